@@ -19,6 +19,8 @@ pub struct App {
     viewport_position: Pos,
     #[serde(skip)]
     cursor_pos: Option<Pos>,
+    #[serde(skip)]
+    arrangement: Option<nodui::Arrangement>,
 }
 
 impl Default for App {
@@ -30,6 +32,7 @@ impl Default for App {
             connections_shape: ConnectionShape::Bezier,
             viewport_position: Pos::default(),
             cursor_pos: None,
+            arrangement: None,
         }
     }
 }
@@ -241,8 +244,14 @@ impl App {
 impl App {
     #[expect(clippy::too_many_lines)]
     fn show_graph(&mut self, ui: &mut egui::Ui) {
+        let mut connection_to_remove = None;
+
         let graph = nodui::GraphEditor::new("graph")
             .show_grid(self.show_grid)
+            .keyboard(nodui::KeyBindings::default())
+            .rewire_connections(true)
+            .connection_snap_radius(16.0)
+            .arrange(self.arrangement.take())
             .show(ui, |ui| {
                 let mut node_command = NodeCommand::None;
 
@@ -390,16 +399,35 @@ impl App {
 
                 let stroke = egui::Stroke::new(3.0, color);
 
-                match self.connections_shape {
-                    ConnectionShape::Line => connections
-                        .iter()
-                        .for_each(|(a, b)| ui.connect_line(&a, &b, stroke)),
-                    ConnectionShape::Bezier => connections
-                        .iter()
-                        .for_each(|(a, b)| ui.connect_bezier(&a, &b, stroke)),
+                for (a, b) in connections.iter() {
+                    let response = match self.connections_shape {
+                        ConnectionShape::Line => ui.connect_line(&a, &b, stroke),
+                        ConnectionShape::Bezier => {
+                            let waypoints = connections.waypoints(a, b);
+                            ui.connect_bezier_through(&a, &b, waypoints, stroke)
+                        }
+                        ConnectionShape::Orthogonal => ui.connect_orthogonal(&a, &b, stroke),
+                        ConnectionShape::Routed => ui.connect_routed(&a, &b, stroke),
+                        ConnectionShape::Gradient => ui.connect_bezier_gradient(&a, &b, 3.0),
+                        ConnectionShape::Flow => {
+                            ui.connect_bezier_flow(&a, &b, stroke, nodui::Flow::default())
+                        }
+                    };
+
+                    if let Some(response) = response {
+                        response.context_menu(|ui| {
+                            if ui.button("Delete connection").clicked() {
+                                connection_to_remove = Some((a, b));
+                            }
+                        });
+                    }
                 }
             });
 
+        if let Some((a, b)) = connection_to_remove {
+            self.graph.connections_mut().disconnect_pair(a, b);
+        }
+
         graph.response.context_menu(|ui| {
             let pos = graph.viewport.viewport_to_graph(ui.min_rect().left_top());
 
@@ -412,8 +440,28 @@ impl App {
                     self.graph.paste_node(pos);
                 }
             });
+
+            ui.add_enabled_ui(graph.selection.len() > 1, |ui| {
+                ui.menu_button("Arrange", |ui| {
+                    if let Some(arrangement) = widget::arrangement_menu(ui) {
+                        self.arrangement = Some(arrangement);
+                    }
+                });
+            });
         });
 
+        if let Some((a, b)) = graph.detached {
+            self.graph.connections_mut().disconnect_pair(a, b);
+        }
+
+        for edit in &graph.waypoint_edits {
+            self.graph.connections_mut().edit_waypoints(edit);
+        }
+
+        for (a, b) in graph.cut {
+            self.graph.connections_mut().disconnect_pair(a, b);
+        }
+
         if let Some((a, b)) = graph.connection {
             self.graph.connections_mut().connect(a, b);
         }
@@ -476,6 +524,10 @@ enum SocketAction {
 enum ConnectionShape {
     Line,
     Bezier,
+    Orthogonal,
+    Routed,
+    Gradient,
+    Flow,
 }
 
 /* -------------------------------------------------------------------------- */
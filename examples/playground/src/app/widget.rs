@@ -17,11 +17,44 @@ pub fn connection_shape(
             .show_ui(ui, move |ui| {
                 ui.selectable_value(value, ConnectionShape::Line, "Line");
                 ui.selectable_value(value, ConnectionShape::Bezier, "Bezier");
+                ui.selectable_value(value, ConnectionShape::Orthogonal, "Orthogonal");
+                ui.selectable_value(value, ConnectionShape::Routed, "Routed");
+                ui.selectable_value(value, ConnectionShape::Gradient, "Gradient");
+                ui.selectable_value(value, ConnectionShape::Flow, "Flow");
             })
             .response
     }
 }
 
+pub fn arrangement_menu(ui: &mut egui::Ui) -> Option<nodui::Arrangement> {
+    use nodui::Arrangement;
+
+    let mut clicked = None;
+
+    for (arrangement, text) in [
+        (Arrangement::AlignLeft, "Align left"),
+        (Arrangement::AlignRight, "Align right"),
+        (Arrangement::AlignTop, "Align top"),
+        (Arrangement::AlignBottom, "Align bottom"),
+        (
+            Arrangement::AlignHorizontalCenters,
+            "Align horizontal centers",
+        ),
+        (Arrangement::AlignVerticalCenters, "Align vertical centers"),
+        (
+            Arrangement::DistributeHorizontally,
+            "Distribute horizontally",
+        ),
+        (Arrangement::DistributeVertically, "Distribute vertically"),
+    ] {
+        if ui.button(text).clicked() {
+            clicked = Some(arrangement);
+        }
+    }
+
+    clicked
+}
+
 pub fn node_side(value: &mut nodui::NodeSide) -> impl egui::Widget + '_ {
     |ui: &mut egui::Ui| {
         let (text, next) = match *value {
@@ -1,7 +1,7 @@
 //! Connection between socket of the graph.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use super::id::SocketId;
 
@@ -10,6 +10,9 @@ use super::id::SocketId;
 pub struct Connections {
     /// The connections between two sockets.
     connections: HashSet<Pair>,
+    /// The reroute points of the connections, ordered from the first socket of the pair.
+    #[serde(default)]
+    waypoints: HashMap<Pair, Vec<nodui::Pos>>,
 }
 
 impl Connections {
@@ -37,6 +40,27 @@ impl Connections {
     //     todo!();
     // }
 
+    /// Remove the connection between those sockets.
+    ///
+    /// Returns `true` if the sockets were connected.
+    pub fn disconnect_pair(&mut self, a: SocketId, b: SocketId) -> bool {
+        self.waypoints.remove(&Pair::new(a, b));
+        self.connections.remove(&Pair::new(a, b))
+    }
+
+    /// The reroute points of the connection between those sockets.
+    pub fn waypoints(&self, a: SocketId, b: SocketId) -> &[nodui::Pos] {
+        self.waypoints
+            .get(&Pair::new(a, b))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Apply an edit of the reroute points made by the user.
+    pub fn edit_waypoints(&mut self, edit: &nodui::WaypointEdit<SocketId>) {
+        let &(a, b) = edit.connection();
+        edit.apply(self.waypoints.entry(Pair::new(a, b)).or_default());
+    }
+
     /// Remove the connection from this socket.
     pub fn disconnect(&mut self, socket: SocketId) {
         self.connections = self
@@ -44,6 +68,7 @@ impl Connections {
             .drain()
             .filter(|pair| !pair.contains(socket))
             .collect();
+        self.waypoints.retain(|pair, _| !pair.contains(socket));
     }
 }
 
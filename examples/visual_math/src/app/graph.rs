@@ -201,6 +201,27 @@ impl GraphApp {
         id
     }
 
+    /// Creates an operation node and connect its first input to the specified socket.
+    pub fn add_op_node_and_connect_first_input(
+        &mut self,
+        pos: Pos,
+        op: Op,
+        socket_to_connect: OutputSocketId,
+    ) -> OpNodeId {
+        let id = self.add_op_node(pos, op);
+
+        let input = self
+            .graph
+            .get_op_node(id)
+            .and_then(|node| node.input_socket_ids().next());
+
+        if let Some(input) = input {
+            self.connect(socket_to_connect.into(), input.into());
+        }
+
+        id
+    }
+
     /// Creates an binary operation node and connect its inputs to the specified sockets.
     pub fn add_binary_op_node_and_connect_input(
         &mut self,
@@ -245,7 +266,7 @@ impl GraphApp {
 impl GraphApp {
     #[allow(clippy::missing_docs_in_private_items)] // TODO: docs
     #[allow(clippy::too_many_lines)]
-    pub fn show_nodes(&mut self, ui: &mut nodui::GraphUi<SocketId>) {
+    pub fn show_nodes(&mut self, ui: &mut nodui::GraphUi<'_, SocketId>) {
         enum Command {
             None,
             Remove(NodeId),
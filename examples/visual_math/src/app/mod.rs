@@ -7,7 +7,7 @@ use core::f32;
 use egui::{CentralPanel, DragValue, Grid, SidePanel, Ui};
 use nodui::Pos;
 
-use crate::graph::{BinaryOp, Op, UnaryOp};
+use crate::graph::{BinaryOp, Connections, Op, OutputSocketId, SocketId, UnaryOp};
 
 use self::graph::GraphApp;
 
@@ -23,6 +23,10 @@ pub struct App {
     #[serde(skip)]
     /// The position we want to look at.
     look_at: Option<Pos>,
+
+    #[serde(skip)]
+    /// The output socket from which a connection has been dropped on the canvas, and where.
+    dropped_connection: Option<(OutputSocketId, Pos)>,
 }
 
 impl Default for App {
@@ -62,6 +66,7 @@ impl Default for App {
 
             current_graph_pos: Pos::default(),
             look_at: None,
+            dropped_connection: None,
         }
     }
 }
@@ -175,22 +180,19 @@ impl App {
 
         let graph = graph
             .show_grid(false)
+            .animate_camera(0.3)
+            .can_connect(|a, b| Connections::can_connect(*a, *b))
+            .connection_snap_radius(16.0)
             .show(ui, |ui| {
                 self.graph.show_nodes(ui);
             })
             .show_connections(|ui| {
                 let color = ui.preferred_color();
 
-                ui.in_progress_connection_line_with_feedback(|source, target| {
-                    if let Some(target) = target {
-                        let color = if crate::graph::Connections::can_connect(source.id, target.id)
-                        {
-                            egui::Color32::GREEN
-                        } else {
-                            egui::Color32::RED
-                        };
-
-                        egui::Stroke::new(5.0, color)
+                // The target is always a socket the source can be connected to.
+                ui.in_progress_connection_line_with_feedback(|_, target| {
+                    if target.is_some() {
+                        egui::Stroke::new(5.0, egui::Color32::GREEN)
                     } else {
                         egui::Stroke::new(3.0, color)
                     }
@@ -213,33 +215,81 @@ impl App {
             self.graph.connect(a, b);
         }
 
+        if let Some((socket, pos)) = graph.dropped {
+            // Only the outputs can be connected to a new node.
+            self.dropped_connection = match socket {
+                SocketId::Output(socket) => Some((socket, pos)),
+                SocketId::Input(_) => None,
+            };
+        }
+
+        if let Some((socket, pos)) = self.dropped_connection {
+            self.dropped_connection_menu(ui, &graph.viewport, socket, pos);
+        }
+
         self.current_graph_pos = graph.position;
     }
 }
 
+impl App {
+    /// Render the menu to create a node connected to the socket from which a connection has been dropped.
+    fn dropped_connection_menu(
+        &mut self,
+        ui: &Ui,
+        viewport: &nodui::Viewport,
+        socket: OutputSocketId,
+        pos: Pos,
+    ) {
+        let mut close = false;
+
+        let response = egui::Area::new(egui::Id::new("dropped connection menu"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(viewport.graph_to_viewport(pos))
+            .show(ui.ctx(), |ui| {
+                egui::Frame::menu(ui.style()).show(ui, |ui| {
+                    new_node_buttons(ui, |op| {
+                        self.graph
+                            .add_op_node_and_connect_first_input(pos, op, socket);
+                        close = true;
+                    });
+                });
+            })
+            .response;
+
+        if close || response.clicked_elsewhere() {
+            self.dropped_connection = None;
+        }
+    }
+}
+
 /// A sub menu with all option to create a new node.
 ///
 /// Call `on_clicked` with the user's choice when an option is selected.
-fn new_node_menu(ui: &mut Ui, mut on_clicked: impl FnMut(Op)) {
-    ui.menu_button("New", |ui| {
-        if ui.button("Neg").clicked() {
-            on_clicked(UnaryOp::Neg.into());
-        }
+fn new_node_menu(ui: &mut Ui, on_clicked: impl FnMut(Op)) {
+    ui.menu_button("New", |ui| new_node_buttons(ui, on_clicked));
+}
 
-        if ui.button("Add").clicked() {
-            on_clicked(BinaryOp::Add.into());
-        }
+/// The buttons of all option to create a new node.
+///
+/// Call `on_clicked` with the user's choice when an option is selected.
+fn new_node_buttons(ui: &mut Ui, mut on_clicked: impl FnMut(Op)) {
+    if ui.button("Neg").clicked() {
+        on_clicked(UnaryOp::Neg.into());
+    }
 
-        if ui.button("Sub").clicked() {
-            on_clicked(BinaryOp::Sub.into());
-        }
+    if ui.button("Add").clicked() {
+        on_clicked(BinaryOp::Add.into());
+    }
 
-        if ui.button("Mul").clicked() {
-            on_clicked(BinaryOp::Mul.into());
-        }
+    if ui.button("Sub").clicked() {
+        on_clicked(BinaryOp::Sub.into());
+    }
 
-        if ui.button("Div").clicked() {
-            on_clicked(BinaryOp::Div.into());
-        }
-    });
+    if ui.button("Mul").clicked() {
+        on_clicked(BinaryOp::Mul.into());
+    }
+
+    if ui.button("Div").clicked() {
+        on_clicked(BinaryOp::Div.into());
+    }
 }
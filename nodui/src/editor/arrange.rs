@@ -0,0 +1,144 @@
+//! Alignment and distribution of the selected nodes.
+
+use std::collections::{HashMap, HashSet};
+
+use egui::{pos2, Id, Rect};
+
+use crate::viewport::CanvasPos;
+
+use super::show::NodeGeometry;
+
+/* -------------------------------------------------------------------------- */
+
+/// A command that arranges the selected nodes.
+///
+/// See [`GraphEditor::arrange`](crate::GraphEditor::arrange).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Arrangement {
+    /// Aligns the left edges of the nodes on the left-most one.
+    AlignLeft,
+    /// Aligns the right edges of the nodes on the right-most one.
+    AlignRight,
+    /// Aligns the top edges of the nodes on the top-most one.
+    AlignTop,
+    /// Aligns the bottom edges of the nodes on the bottom-most one.
+    AlignBottom,
+    /// Aligns the centers of the nodes on a vertical line, at the center of the selection.
+    AlignHorizontalCenters,
+    /// Aligns the centers of the nodes on a horizontal line, at the center of the selection.
+    AlignVerticalCenters,
+    /// Spaces the nodes evenly between the left-most and the right-most ones.
+    DistributeHorizontally,
+    /// Spaces the nodes evenly between the top-most and the bottom-most ones.
+    DistributeVertically,
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Computes where the selected nodes are moved by `arrangement`.
+///
+/// Uses the geometry of the nodes rendered during the last frame, the other nodes are ignored.
+pub(super) fn placements(
+    arrangement: Arrangement,
+    selection: &HashSet<Id>,
+    geometries: &HashMap<Id, NodeGeometry>,
+) -> HashMap<Id, CanvasPos> {
+    let nodes: Vec<(Id, Rect)> = selection
+        .iter()
+        .filter_map(|id| {
+            let geometry = geometries.get(id)?;
+            let min = geometry.canvas_pos().to_vec2().to_pos2();
+            Some((*id, Rect::from_min_size(min, geometry.canvas_size())))
+        })
+        .collect();
+
+    let Some(bounds) = nodes.iter().map(|(_, rect)| *rect).reduce(Rect::union) else {
+        return HashMap::new();
+    };
+
+    let align = |rect: Rect| match arrangement {
+        Arrangement::AlignLeft => pos2(bounds.min.x, rect.min.y),
+        Arrangement::AlignRight => pos2(bounds.max.x - rect.width(), rect.min.y),
+        Arrangement::AlignTop => pos2(rect.min.x, bounds.min.y),
+        Arrangement::AlignBottom => pos2(rect.min.x, bounds.max.y - rect.height()),
+        Arrangement::AlignHorizontalCenters => {
+            pos2(bounds.center().x - rect.width() / 2.0, rect.min.y)
+        }
+        Arrangement::AlignVerticalCenters => {
+            pos2(rect.min.x, bounds.center().y - rect.height() / 2.0)
+        }
+        // Handled by `distribute`.
+        Arrangement::DistributeHorizontally | Arrangement::DistributeVertically => rect.min,
+    };
+
+    match arrangement {
+        Arrangement::DistributeHorizontally => distribute(nodes, Axis::X),
+        Arrangement::DistributeVertically => distribute(nodes, Axis::Y),
+        Arrangement::AlignLeft
+        | Arrangement::AlignRight
+        | Arrangement::AlignTop
+        | Arrangement::AlignBottom
+        | Arrangement::AlignHorizontalCenters
+        | Arrangement::AlignVerticalCenters => nodes
+            .into_iter()
+            .map(|(id, rect)| (id, CanvasPos::ZERO + align(rect).to_vec2()))
+            .collect(),
+    }
+}
+
+/// An axis of the canvas.
+#[derive(Clone, Copy)]
+enum Axis {
+    /// The horizontal axis.
+    X,
+    /// The vertical axis.
+    Y,
+}
+
+impl Axis {
+    /// The coordinate of `pos` along this axis.
+    fn of(self, pos: egui::Pos2) -> f32 {
+        match self {
+            Axis::X => pos.x,
+            Axis::Y => pos.y,
+        }
+    }
+
+    /// Sets the coordinate of `pos` along this axis.
+    fn with(self, pos: egui::Pos2, value: f32) -> egui::Pos2 {
+        match self {
+            Axis::X => pos2(value, pos.y),
+            Axis::Y => pos2(pos.x, value),
+        }
+    }
+}
+
+/// Spaces the nodes evenly along `axis`, the first and the last nodes don't move.
+fn distribute(mut nodes: Vec<(Id, Rect)>, axis: Axis) -> HashMap<Id, CanvasPos> {
+    nodes.sort_by(|(_, a), (_, b)| axis.of(a.center()).total_cmp(&axis.of(b.center())));
+
+    let (Some((_, first)), Some((_, last))) = (nodes.first(), nodes.last()) else {
+        return HashMap::new();
+    };
+
+    let length = |rect: &Rect| axis.of(rect.max) - axis.of(rect.min);
+
+    let span = axis.of(last.max) - axis.of(first.min);
+    let occupied: f32 = nodes.iter().map(|(_, rect)| length(rect)).sum();
+
+    #[allow(clippy::cast_precision_loss)]
+    let gap = (span - occupied) / nodes.len().saturating_sub(1).max(1) as f32;
+
+    let mut start = axis.of(first.min);
+
+    nodes
+        .into_iter()
+        .map(|(id, rect)| {
+            let min = axis.with(rect.min, start);
+            start += length(&rect) + gap;
+            (id, CanvasPos::ZERO + min.to_vec2())
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,103 @@
+//! Paths parametrized by their arc length.
+
+use egui::Pos2;
+
+/* -------------------------------------------------------------------------- */
+
+/// A path parametrized by the distance along it.
+pub(super) struct ArcLengthPath {
+    /// The points of the path.
+    pub(super) points: Vec<Pos2>,
+    /// The distance along the path of each point.
+    distances: Vec<f32>,
+}
+
+impl ArcLengthPath {
+    /// Creates the path going through `points`.
+    pub(super) fn new(points: Vec<Pos2>) -> Self {
+        let mut distance = 0.0;
+
+        let distances = core::iter::once(0.0)
+            .chain(points.windows(2).map(|segment| {
+                if let [from, to] = segment {
+                    distance += from.distance(*to);
+                }
+                distance
+            }))
+            .take(points.len())
+            .collect();
+
+        ArcLengthPath { points, distances }
+    }
+
+    /// The length of the path.
+    pub(super) fn length(&self) -> f32 {
+        self.distances.last().copied().unwrap_or(0.0)
+    }
+
+    /// The point at `distance` along the path.
+    ///
+    /// `distance` is clamped to the path.
+    pub(super) fn point_at(&self, distance: f32) -> Pos2 {
+        // The index of the end of the segment that contains `distance`.
+        let index = self
+            .distances
+            .partition_point(|d| *d < distance)
+            .clamp(1, self.points.len().saturating_sub(1).max(1));
+
+        let segment = (
+            self.points.get(index - 1),
+            self.points.get(index),
+            self.distances.get(index - 1),
+            self.distances.get(index),
+        );
+
+        match segment {
+            (Some(from), Some(to), Some(from_distance), Some(to_distance))
+                if from_distance < to_distance =>
+            {
+                let t = (distance - from_distance) / (to_distance - from_distance);
+                from.lerp(*to, t.clamp(0.0, 1.0))
+            }
+            (Some(point), ..) => *point,
+            (None, ..) => Pos2::ZERO,
+        }
+    }
+
+    /// The part of the path between the distances `from` and `to`.
+    ///
+    /// The distances are clamped to the path.
+    pub(super) fn sub_path(&self, from: f32, to: f32) -> Vec<Pos2> {
+        let from = from.max(0.0);
+        let to = to.min(self.length());
+
+        if from >= to {
+            return Vec::new();
+        }
+
+        let inner = self
+            .points
+            .iter()
+            .zip(&self.distances)
+            .filter(|(_, distance)| from < **distance && **distance < to)
+            .map(|(point, _)| *point);
+
+        core::iter::once(self.point_at(from))
+            .chain(inner)
+            .chain(core::iter::once(self.point_at(to)))
+            .collect()
+    }
+
+    /// The segments of the path, with the distance along the path of their middle.
+    pub(super) fn segments(&self) -> impl Iterator<Item = (Pos2, Pos2, f32)> + '_ {
+        self.points
+            .windows(2)
+            .zip(&self.distances)
+            .filter_map(|(segment, start)| match segment {
+                [from, to] => Some((*from, *to, start + from.distance(*to) / 2.0)),
+                _ => None,
+            })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,215 @@
+//! Animated connections.
+
+use egui::{Rect, Response, Shape, Stroke};
+
+use super::{arc_length::ArcLengthPath, bezier_curve, side_direction, ConnectionsUi};
+
+/* -------------------------------------------------------------------------- */
+
+/// The animated stroke of a connection, whose pattern moves along the connection over time.
+///
+/// See [`ConnectionsUi::connect_bezier_flow`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Flow {
+    /// The pattern of the stroke.
+    pub pattern: FlowPattern,
+    /// The speed of the pattern, in pixels per second at zoom `1.0`.
+    pub speed: f32,
+    /// Whether the pattern moves from the second socket to the first one.
+    pub reversed: bool,
+}
+
+/// The pattern of a [`Flow`].
+///
+/// The lengths are in pixels at zoom `1.0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FlowPattern {
+    /// Dashes separated by gaps.
+    Dashed {
+        /// The length of the dashes.
+        dash: f32,
+        /// The length of the gaps between the dashes.
+        gap: f32,
+    },
+    /// Dots of the width of the stroke.
+    Dotted {
+        /// The distance between the centers of the dots.
+        spacing: f32,
+    },
+}
+
+impl Default for Flow {
+    #[inline]
+    fn default() -> Self {
+        Self::dashed(10.0, 5.0)
+    }
+}
+
+impl Flow {
+    /// A flow with dashes of length `dash` separated by `gap`, in pixels at zoom `1.0`.
+    #[inline]
+    #[must_use]
+    pub fn dashed(dash: f32, gap: f32) -> Self {
+        Self {
+            pattern: FlowPattern::Dashed { dash, gap },
+            speed: 20.0,
+            reversed: false,
+        }
+    }
+
+    /// A flow with dots separated by `spacing`, in pixels at zoom `1.0`.
+    #[inline]
+    #[must_use]
+    pub fn dotted(spacing: f32) -> Self {
+        Self {
+            pattern: FlowPattern::Dotted { spacing },
+            speed: 20.0,
+            reversed: false,
+        }
+    }
+
+    /// The speed of the pattern, in pixels per second at zoom `1.0`.
+    ///
+    /// The pattern doesn't move if `speed` is `0.0`.
+    #[must_use]
+    #[inline]
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Whether the pattern moves from the second socket to the first one.
+    #[must_use]
+    #[inline]
+    pub fn reversed(mut self, reversed: bool) -> Self {
+        self.reversed = reversed;
+        self
+    }
+
+    /// The length of the pattern before it repeats itself.
+    fn period(&self) -> f32 {
+        match self.pattern {
+            FlowPattern::Dashed { dash, gap } => dash + gap,
+            FlowPattern::Dotted { spacing } => spacing,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl<S> ConnectionsUi<S>
+where
+    S: PartialEq + Clone + core::hash::Hash,
+{
+    /// Render the connection between two sockets with a bezier curve and an animated `flow`.
+    ///
+    /// By default the pattern of the flow moves from `a` to `b`, see [`Flow::reversed`].
+    /// The editor is repainted continuously while an animated connection is visible.
+    ///
+    /// The stroke width and the pattern are scaled by the [zoom factor](Self::zoom).
+    /// A pattern shorter than the stroke width, or than a pixel, is rendered as a solid line.
+    ///
+    /// Returns the [`Response`] of the connection, or `None` if a socket has not been rendered.
+    /// See [`Self::interact_connection`].
+    #[inline]
+    pub fn connect_bezier_flow(
+        &mut self,
+        a: &S,
+        b: &S,
+        stroke: impl Into<Stroke>,
+        flow: Flow,
+    ) -> Option<Response> {
+        let (a_end, b_end) = self.endpoints(a, b)?;
+
+        let zoom = self.viewport.zoom;
+
+        let mut stroke = stroke.into();
+        stroke.width *= zoom;
+
+        let bezier = bezier_curve(
+            a_end.pos,
+            side_direction(a_end.side),
+            b_end.pos,
+            side_direction(b_end.side),
+            stroke.into(),
+        );
+        let path = ArcLengthPath::new(bezier.flatten(None));
+
+        let period = flow.period() * zoom;
+
+        // Shorter patterns would be indistinguishable from a solid line and need a lot of shapes.
+        if period >= stroke.width.max(1.0) {
+            let time = self.ui.input(|i| i.time);
+            let distance = time * f64::from(flow.speed * zoom);
+
+            #[allow(clippy::cast_possible_truncation)]
+            let offset = distance.rem_euclid(f64::from(period)) as f32;
+            let offset = if flow.reversed {
+                period - offset
+            } else {
+                offset
+            };
+
+            self.paint_flow(&path, flow.pattern, offset, zoom, stroke);
+
+            let is_visible = self
+                .ui
+                .clip_rect()
+                .intersects(Rect::from_points(&path.points));
+
+            if flow.speed != 0.0 && is_visible {
+                self.ui.ctx().request_repaint();
+            }
+        } else {
+            self.painter.add(Shape::line(path.points.clone(), stroke));
+        }
+
+        Some(self.interact_connection(a, b, &path.points, stroke.width))
+    }
+
+    /// Paints the `pattern` along the `path`, starting `offset` pixels after the start of the path.
+    fn paint_flow(
+        &self,
+        path: &ArcLengthPath,
+        pattern: FlowPattern,
+        offset: f32,
+        zoom: f32,
+        stroke: Stroke,
+    ) {
+        let length = path.length();
+
+        match pattern {
+            FlowPattern::Dashed { dash, gap } => {
+                let dash = dash * zoom;
+                let period = dash + gap * zoom;
+
+                // The first dash may start before the path.
+                let mut start = offset - period;
+
+                while start < length {
+                    let points = path.sub_path(start, start + dash);
+                    if points.len() > 1 {
+                        self.painter.add(Shape::line(points, stroke));
+                    }
+                    start += period;
+                }
+            }
+            FlowPattern::Dotted { spacing } => {
+                let spacing = spacing * zoom;
+
+                let mut distance = offset;
+
+                while distance <= length {
+                    self.painter.circle_filled(
+                        path.point_at(distance),
+                        stroke.width / 2.0,
+                        stroke.color,
+                    );
+                    distance += spacing;
+                }
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
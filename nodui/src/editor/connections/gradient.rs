@@ -0,0 +1,50 @@
+//! Connections colored by their sockets.
+
+use egui::{epaint::PathStroke, Response, Shape, Stroke};
+
+use super::{arc_length::ArcLengthPath, bezier_curve, side_direction, ConnectionsUi};
+
+/* -------------------------------------------------------------------------- */
+
+impl<S> ConnectionsUi<S>
+where
+    S: PartialEq + Clone + core::hash::Hash,
+{
+    /// Render the connection between two sockets with a bezier curve whose color blends
+    /// from the color of `a` to the color of `b` along the curve.
+    ///
+    /// The colors are the ones of the [rendered sockets](crate::RenderedSocket::color).
+    ///
+    /// The stroke `width` is scaled by the [zoom factor](Self::zoom).
+    ///
+    /// Returns the [`Response`] of the connection, or `None` if a socket has not been rendered.
+    /// See [`Self::interact_connection`].
+    #[inline]
+    pub fn connect_bezier_gradient(&mut self, a: &S, b: &S, width: f32) -> Option<Response> {
+        let (a_end, b_end) = self.endpoints(a, b)?;
+
+        let width = width * self.viewport.zoom;
+
+        let bezier = bezier_curve(
+            a_end.pos,
+            side_direction(a_end.side),
+            b_end.pos,
+            side_direction(b_end.side),
+            PathStroke::NONE,
+        );
+
+        let path = ArcLengthPath::new(bezier.flatten(None));
+        let length = path.length().max(f32::EPSILON);
+
+        // Each segment has the color of its middle.
+        self.painter
+            .extend(path.segments().map(|(from, to, distance)| {
+                let color = a_end.color.lerp_to_gamma(b_end.color, distance / length);
+                Shape::line_segment([from, to], Stroke::new(width, color))
+            }));
+
+        Some(self.interact_connection(a, b, &path.points, width))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,142 @@
+//! Interactions with the connections.
+
+use egui::{response::Flags, Id, Pos2, Rect, Response};
+
+/* -------------------------------------------------------------------------- */
+
+/// Hit-tests the connections against the pointer.
+pub(super) struct ConnectionsInteraction {
+    /// The response of the editor, used as a base for the responses of the connections.
+    editor_response: Response,
+    /// The connection that was the closest to the pointer during the last frame.
+    hovered: Option<Id>,
+    /// The connection closest to the pointer so far during this frame, and its distance to the pointer.
+    closest: Option<(Id, f32)>,
+    /// Whether a connection has received the click of the user.
+    clicked: bool,
+}
+
+/// The flags a connection takes from the editor response when it is hovered.
+const HOVERED_FLAGS: Flags = Flags::HOVERED
+    .union(Flags::CONTAINS_POINTER)
+    .union(Flags::CLICKED)
+    .union(Flags::FAKE_PRIMARY_CLICKED)
+    .union(Flags::LONG_TOUCHED)
+    .union(Flags::IS_POINTER_BUTTON_DOWN_ON);
+
+/// The flags that represent a click.
+const CLICK_FLAGS: Flags = Flags::CLICKED
+    .union(Flags::FAKE_PRIMARY_CLICKED)
+    .union(Flags::LONG_TOUCHED);
+
+impl ConnectionsInteraction {
+    /// Creates a [`ConnectionsInteraction`].
+    ///
+    /// `hovered` is the connection hovered during the last frame.
+    pub(super) fn new(editor_response: Response, hovered: Option<Id>) -> Self {
+        Self {
+            editor_response,
+            hovered,
+            closest: None,
+            clicked: false,
+        }
+    }
+
+    /// Hit-tests a connection rendered with a path of `width` pixels.
+    ///
+    /// Only the connection closest to the pointer is hovered.
+    pub(super) fn interact(&mut self, id: Id, points: &[Pos2], width: f32) -> Response {
+        let editor = &self.editor_response;
+
+        let tolerance = width / 2.0 + editor.ctx.style().interaction.interact_radius;
+
+        let distance = editor
+            .hover_pos()
+            .filter(|_| editor.contains_pointer())
+            .map(|pointer_pos| distance_to_path(pointer_pos, points))
+            .filter(|distance| *distance <= tolerance);
+
+        if let Some(distance) = distance {
+            if !self.closest.is_some_and(|(_, closest)| closest <= distance) {
+                self.closest = Some((id, distance));
+            }
+        }
+
+        let is_hovered = distance.is_some() && self.hovered == Some(id);
+
+        let rect = Rect::from_points(points).expand(tolerance);
+
+        let mut response = editor.clone();
+        response.id = id;
+        response.rect = rect;
+        response.interact_rect = rect;
+        response.sense = egui::Sense::click();
+        response.flags = editor.flags & Flags::ENABLED;
+
+        if is_hovered {
+            response.flags |= editor.flags & HOVERED_FLAGS;
+            self.clicked |= editor.flags.intersects(CLICK_FLAGS);
+        } else {
+            response.interact_pointer_pos = None;
+        }
+
+        response
+    }
+
+    /// Returns the connection hovered during this frame and the response of the editor.
+    ///
+    /// If a connection has been clicked, the click is removed from the editor response.
+    pub(super) fn finish(self) -> (Option<Id>, Response) {
+        let Self {
+            mut editor_response,
+            hovered,
+            closest,
+            clicked,
+        } = self;
+
+        let new_hovered = closest.map(|(id, _)| id);
+
+        if new_hovered != hovered {
+            // The hovered connection is known only at the end of the frame.
+            editor_response.ctx.request_repaint();
+        }
+
+        if clicked {
+            editor_response.flags.remove(CLICK_FLAGS);
+        }
+
+        (new_hovered, editor_response)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The distance between `pos` and a path.
+pub(super) fn distance_to_path(pos: Pos2, points: &[Pos2]) -> f32 {
+    match points {
+        [] => f32::INFINITY,
+        [point] => pos.distance(*point),
+        _ => points
+            .windows(2)
+            .map(|segment| match segment {
+                [a, b] => distance_to_segment(pos, *a, *b),
+                _ => f32::INFINITY,
+            })
+            .fold(f32::INFINITY, f32::min),
+    }
+}
+
+/// The distance between `pos` and the segment `[a, b]`.
+fn distance_to_segment(pos: Pos2, a: Pos2, b: Pos2) -> f32 {
+    let ab = b - a;
+    let length_sq = ab.length_sq();
+
+    if length_sq <= f32::EPSILON {
+        return pos.distance(a);
+    }
+
+    let t = ((pos - a).dot(ab) / length_sq).clamp(0.0, 1.0);
+    pos.distance(a + ab * t)
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,482 @@
+//! Rendering of connections.
+
+mod arc_length;
+mod flow;
+mod gradient;
+mod interact;
+mod orthogonal;
+mod routing;
+mod waypoints;
+
+use egui::{
+    epaint::{CubicBezierShape, PathStroke},
+    Color32, Id, LayerId, Pos2, Response, Shape, Stroke,
+};
+
+use crate::{socket::SocketInteraction, ConnectionInProgress, NodeSide};
+
+use super::{show::KnifeLine, stages, GraphEditor, GraphResponse, RenderedSocket, Viewport};
+
+pub use flow::{Flow, FlowPattern};
+use interact::ConnectionsInteraction;
+pub(super) use routing::RouteCache;
+pub use waypoints::WaypointEdit;
+
+/* -------------------------------------------------------------------------- */
+
+impl<S> GraphEditor<stages::Connections<S>> {
+    /// Render the connections between the sockets.
+    #[inline]
+    pub fn show_connections(self, build_fn: impl FnOnce(&mut ConnectionsUi<S>)) -> GraphResponse<S>
+    where
+        S: Send + Sync + Clone + 'static,
+    {
+        let Self {
+            id,
+            stage:
+                stages::Connections {
+                    ui,
+                    mut state,
+                    response,
+                    viewport,
+                    sockets,
+                    socket_interaction,
+                    knife,
+                    nodes_rect,
+                    selection_changed,
+                },
+        } = self;
+
+        let mut connection = None;
+        let mut in_progress = None;
+        let mut detached = None;
+        let mut dropped = None;
+
+        match socket_interaction {
+            SocketInteraction::None => {}
+            SocketInteraction::Connect(a, b) => connection = Some((a, b)),
+            SocketInteraction::InProgress(interaction) => in_progress = Some(interaction),
+            SocketInteraction::Detach(a, b) => detached = Some((a, b)),
+            SocketInteraction::Dropped(socket, pos) => {
+                dropped = Some((socket, viewport.viewport_to_graph(pos)));
+            }
+        }
+
+        let mut routes = core::mem::take(&mut state.routes);
+        routes.update(&state.node_geometries);
+
+        let layer_id = LayerId::new(egui::Order::Background, id);
+        let mut painter = ui.painter().clone();
+        painter.set_layer_id(layer_id);
+
+        let mut connections_ui = ConnectionsUi {
+            preferred_color: ui.visuals().strong_text_color(),
+            viewport,
+            graph_id: id,
+            ui,
+            painter,
+            sockets,
+            connection: in_progress,
+            interaction: ConnectionsInteraction::new(response, state.hovered_connection),
+            knife,
+            routes,
+            drawn: Vec::new(),
+            cut: Vec::new(),
+            waypoint_edits: Vec::new(),
+        };
+        build_fn(&mut connections_ui);
+
+        // If user didn't render the in progress connection, we do it for them.
+        connections_ui
+            .in_progress_connection_line(Stroke::new(5.0, connections_ui.preferred_color()));
+
+        let ConnectionsUi {
+            preferred_color: _,
+            viewport,
+            graph_id: _,
+            ui: _,
+            painter: _,
+            sockets,
+            connection: _,
+            interaction,
+            knife: _,
+            mut routes,
+            drawn,
+            cut,
+            waypoint_edits,
+        } = connections_ui;
+
+        state.connections = drawn;
+        routes.prune();
+        state.routes = routes;
+
+        let (hovered_connection, response) = interaction.finish();
+        state.hovered_connection = hovered_connection;
+
+        let position = viewport.grid.canvas_to_graph(state.camera.position);
+        let zoom = viewport.zoom;
+        let viewport_state = state.camera;
+        let is_animating = state.camera_animation.is_some();
+        let selection = state.selection.clone();
+
+        GraphResponse {
+            viewport,
+            response,
+            sockets,
+            connection,
+            detached,
+            dropped,
+            cut,
+            waypoint_edits,
+            position,
+            zoom,
+            viewport_state,
+            nodes_rect,
+            is_animating,
+            selection,
+            selection_changed,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// This is what you use to render the connections.
+pub struct ConnectionsUi<S> {
+    /// A good default color for connections that matches the current theme.
+    preferred_color: Color32,
+    /// The viewport of the editor.
+    viewport: Viewport,
+    /// The id of the graph editor.
+    graph_id: Id,
+    /// The [`egui::Ui`] used to render the editor.
+    ui: egui::Ui,
+    /// The painter we want to render to.
+    painter: egui::Painter,
+    /// The rendered sockets.
+    sockets: Vec<RenderedSocket<S>>,
+    /// A in-progress connection that have to be rendered.
+    connection: Option<ConnectionInProgress<S>>,
+    /// The hit-test of the connections.
+    interaction: ConnectionsInteraction,
+    /// The line of the knife released during this frame, if any.
+    knife: Option<KnifeLine>,
+    /// The connections routed around the nodes.
+    routes: RouteCache,
+    /// The connections rendered so far.
+    drawn: Vec<(S, S)>,
+    /// The connections crossed by the knife.
+    cut: Vec<(S, S)>,
+    /// The edits of the waypoints made by the user.
+    waypoint_edits: Vec<WaypointEdit<S>>,
+}
+
+/// An end of a connection.
+#[derive(Clone, Copy)]
+struct Endpoint {
+    /// The UI position of the socket.
+    pos: Pos2,
+    /// On which side of the node the socket is rendered.
+    side: NodeSide,
+    /// The color of the socket.
+    color: Color32,
+}
+
+impl<S> ConnectionsUi<S> {
+    /// A good default color for connections that matches the current theme.
+    #[inline]
+    pub fn preferred_color(&self) -> Color32 {
+        self.preferred_color
+    }
+
+    /// The zoom factor of the viewport.
+    ///
+    /// The `connect_*` and `in_progress_connection_*` methods already scale the stroke width
+    /// by this factor, use it when rendering with the low-level API.
+    #[inline]
+    pub fn zoom(&self) -> f32 {
+        self.viewport.zoom
+    }
+}
+
+impl<S> ConnectionsUi<S> {
+    /// Render the connection the user is currently doing.
+    ///
+    /// If called multiple times, only the first call will have effect.
+    ///
+    /// # Low-level API
+    ///
+    /// This methods is a low-level API, it gives you direct access to the [`egui::Painter`].
+    /// Prefer the usage of other `in_progress_connection_*` methods instead.
+    #[inline]
+    pub fn in_progress_connection(
+        &mut self,
+        show: impl FnOnce(&egui::Painter, ConnectionInProgress<S>),
+    ) {
+        if let Some(connection) = self.connection.take() {
+            let mut top_most_painter = self.painter.clone();
+            top_most_painter.set_layer_id(LayerId::new(
+                egui::Order::Tooltip,
+                self.painter.layer_id().id,
+            ));
+            show(&top_most_painter, connection);
+        }
+    }
+
+    /// Render the in-progress connection with a straight line.
+    ///
+    /// The stroke width is scaled by the [zoom factor](Self::zoom).
+    ///
+    /// See [`Self::in_progress_connection`].
+    #[inline]
+    pub fn in_progress_connection_line(&mut self, stroke: impl Into<Stroke>) {
+        let zoom = self.viewport.zoom;
+        self.in_progress_connection(|painter, connection| {
+            let points = [connection.source.pos(), connection.end_pos()];
+            let mut stroke = stroke.into();
+            stroke.width *= zoom;
+
+            painter.add(Shape::LineSegment { points, stroke });
+        });
+    }
+
+    /// Render the in-progress connection with a straight line with a stroke based on
+    /// the source socket and the socket currently being hovered.
+    ///
+    /// The stroke width is scaled by the [zoom factor](Self::zoom).
+    ///
+    /// See [`Self::in_progress_connection`].
+    ///
+    /// # Example
+    ///
+    /// In this example, we use the feedback callback to change the color and width of the line
+    /// the signal the user the pointer is hovering a socket.
+    ///
+    /// ```
+    /// # fn foo<S: PartialEq + Send + Sync + Clone + 'static>(graph: nodui::GraphEditor<nodui::stages::Connections::<S>>) {
+    /// graph.show_connections(|ui| {
+    ///     let preferred_color = ui.preferred_color();
+    ///     ui.in_progress_connection_line_with_feedback(|_, target| {
+    ///         if target.is_some() {
+    ///             egui::Stroke::new(5.0, egui::Color32::GREEN)
+    ///         } else {
+    ///             egui::Stroke::new(3.0, preferred_color)
+    ///         }
+    ///     });
+    /// });
+    /// # }
+    /// ```
+    #[inline]
+    pub fn in_progress_connection_line_with_feedback(
+        &mut self,
+        stroke: impl FnOnce(RenderedSocket<S>, Option<RenderedSocket<S>>) -> Stroke,
+    ) {
+        let zoom = self.viewport.zoom;
+        self.in_progress_connection(|painter, connection| {
+            let points = [connection.source.pos(), connection.end_pos()];
+
+            let ConnectionInProgress {
+                source,
+                target,
+                pointer_pos: _,
+            } = connection;
+
+            let mut stroke = stroke(source, target);
+            stroke.width *= zoom;
+
+            painter.add(Shape::LineSegment { points, stroke });
+        });
+    }
+}
+
+impl<S> ConnectionsUi<S>
+where
+    S: PartialEq + Clone + core::hash::Hash,
+{
+    /// Render the connection between two sockets.
+    ///
+    /// The method receive the id of the two sockets to connected and call the callback function
+    /// which receive the [`Painter`](egui::Painter) to render to and the [`RenderedSocket`] of the
+    /// two sockets.
+    ///
+    /// # Example
+    ///
+    /// Here's an example of connecting two sockets with a straight line using the
+    /// color of the socket being at the right of its node.
+    ///
+    /// ```
+    /// # fn foo(ui: &mut nodui::ConnectionsUi<()>, a: &(), b: &()) {
+    /// ui.connect_with(a, b, |painter, a, b| {
+    ///     let stroke = if a.side == nodui::NodeSide::Right {
+    ///         egui::Stroke::new(3.0, a.color)
+    ///     } else {
+    ///         egui::Stroke::new(3.0, b.color)
+    ///     };
+    ///
+    ///     painter.add(egui::Shape::LineSegment {
+    ///         points: [a.pos(), b.pos()],
+    ///         stroke: stroke.into(),
+    ///     });
+    /// });
+    /// # }
+    /// ```
+    ///
+    /// # Low-level API
+    ///
+    /// This methods is a low-level API, it gives you direct access to the [`egui::Painter`].
+    /// Prefer the usage of other `connect_*` methods instead.
+    #[inline]
+    pub fn connect_with(
+        &mut self,
+        a: &S,
+        b: &S,
+        show: impl FnOnce(&egui::Painter, &RenderedSocket<S>, &RenderedSocket<S>),
+    ) {
+        if let Some(a) = self.sockets.iter().find(|s| &s.id == a) {
+            if let Some(b) = self.sockets.iter().find(|s| &s.id == b) {
+                show(&self.painter, a, b);
+                self.drawn.push((a.id.clone(), b.id.clone()));
+            }
+        }
+    }
+
+    /// Hit-tests a connection rendered along the path `points` with a stroke of `width` pixels.
+    ///
+    /// Use it to interact with the connections rendered with [`Self::connect_with`].
+    /// The `connect_*` methods already call it.
+    ///
+    /// Only the connection closest to the pointer is hovered.
+    /// If the connection is clicked, the click is removed from [`GraphResponse::response`].
+    ///
+    /// If the user cuts the connection with the knife, it is reported in [`GraphResponse::cut`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn foo(ui: &mut nodui::ConnectionsUi<()>, a: &(), b: &()) {
+    /// if let Some(response) = ui.connect_line(a, b, egui::Stroke::new(3.0, egui::Color32::WHITE)) {
+    ///     response.context_menu(|ui| {
+    ///         if ui.button("Delete connection").clicked() {
+    ///             /* ... */
+    ///         }
+    ///     });
+    /// }
+    /// # }
+    /// ```
+    #[inline]
+    pub fn interact_connection(&mut self, a: &S, b: &S, points: &[Pos2], width: f32) -> Response {
+        if self
+            .knife
+            .as_ref()
+            .is_some_and(|knife| knife.crosses(points))
+        {
+            self.cut.push((a.clone(), b.clone()));
+        }
+
+        let id = self.graph_id.with(("connection", a, b));
+        self.interaction.interact(id, points, width)
+    }
+
+    /// Finds the rendered sockets to connect and records the connection.
+    fn endpoints(&mut self, a: &S, b: &S) -> Option<(Endpoint, Endpoint)> {
+        let endpoint = |id: &S| {
+            self.sockets.iter().find(|s| &s.id == id).map(|s| Endpoint {
+                pos: s.pos(),
+                side: s.side,
+                color: s.color,
+            })
+        };
+
+        let endpoints = (endpoint(a)?, endpoint(b)?);
+        self.drawn.push((a.clone(), b.clone()));
+
+        Some(endpoints)
+    }
+
+    /// Render the connection between two sockets with a straight line.
+    ///
+    /// The stroke width is scaled by the [zoom factor](Self::zoom).
+    ///
+    /// Returns the [`Response`] of the connection, or `None` if a socket has not been rendered.
+    /// See [`Self::interact_connection`].
+    #[inline]
+    pub fn connect_line(&mut self, a: &S, b: &S, stroke: impl Into<Stroke>) -> Option<Response> {
+        let (a_end, b_end) = self.endpoints(a, b)?;
+
+        let mut stroke = stroke.into();
+        stroke.width *= self.viewport.zoom;
+
+        let points = [a_end.pos, b_end.pos];
+        self.painter.add(Shape::LineSegment { points, stroke });
+
+        Some(self.interact_connection(a, b, &points, stroke.width))
+    }
+
+    /// Render the connection between two sockets with a bezier curve.
+    ///
+    /// The stroke width is scaled by the [zoom factor](Self::zoom).
+    ///
+    /// Returns the [`Response`] of the connection, or `None` if a socket has not been rendered.
+    /// See [`Self::interact_connection`].
+    #[inline]
+    pub fn connect_bezier(
+        &mut self,
+        a: &S,
+        b: &S,
+        stroke: impl Into<PathStroke>,
+    ) -> Option<Response> {
+        let (a_end, b_end) = self.endpoints(a, b)?;
+
+        let mut stroke = stroke.into();
+        stroke.width *= self.viewport.zoom;
+        let width = stroke.width;
+
+        let bezier = bezier_curve(
+            a_end.pos,
+            side_direction(a_end.side),
+            b_end.pos,
+            side_direction(b_end.side),
+            stroke,
+        );
+
+        let points = bezier.flatten(None);
+        self.painter.add(bezier);
+
+        Some(self.interact_connection(a, b, &points, width))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The horizontal direction of a connection leaving a socket on `side`.
+fn side_direction(side: NodeSide) -> f32 {
+    match side {
+        NodeSide::Left => -1.0,
+        NodeSide::Right => 1.0,
+    }
+}
+
+/// Creates a bezier curve from `a` to `b` with horizontal tangents.
+///
+/// `a_dir` and `b_dir` are the horizontal directions of the control points of `a` and `b`.
+fn bezier_curve(a: Pos2, a_dir: f32, b: Pos2, b_dir: f32, stroke: PathStroke) -> CubicBezierShape {
+    let control_scale = (a.x - b.x).abs() / 2.0;
+
+    let a_control = Pos2 {
+        x: a.x + a_dir * control_scale,
+        y: a.y,
+    };
+    let b_control = Pos2 {
+        x: b.x + b_dir * control_scale,
+        y: b.y,
+    };
+
+    CubicBezierShape::from_points_stroke(
+        [a, a_control, b_control, b],
+        false,
+        Color32::TRANSPARENT,
+        stroke,
+    )
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,154 @@
+//! Schematic-style connections, made of horizontal and vertical segments.
+
+use egui::{epaint::PathStroke, pos2, Pos2, Response, Shape};
+
+use crate::NodeSide;
+
+use super::{side_direction, ConnectionsUi};
+
+/* -------------------------------------------------------------------------- */
+
+/// The minimal length of the segment leaving a socket, in pixels at zoom `1.0`.
+pub(super) const ORTHOGONAL_STUB_LENGTH: f32 = 20.0;
+
+/// The radius of the rounded corners, in pixels at zoom `1.0`.
+pub(super) const ORTHOGONAL_CORNER_RADIUS: f32 = 8.0;
+
+/// The number of points used to render a rounded corner.
+const CORNER_SEGMENTS: u8 = 6;
+
+impl<S> ConnectionsUi<S>
+where
+    S: PartialEq + Clone + core::hash::Hash,
+{
+    /// Render the connection between two sockets with horizontal and vertical segments and rounded corners.
+    ///
+    /// The connection leaves each socket horizontally, toward the [side](NodeSide) of its node.
+    ///
+    /// The stroke width is scaled by the [zoom factor](Self::zoom).
+    ///
+    /// Returns the [`Response`] of the connection, or `None` if a socket has not been rendered.
+    /// See [`Self::interact_connection`].
+    #[inline]
+    pub fn connect_orthogonal(
+        &mut self,
+        a: &S,
+        b: &S,
+        stroke: impl Into<PathStroke>,
+    ) -> Option<Response> {
+        let (a_end, b_end) = self.endpoints(a, b)?;
+
+        let zoom = self.viewport.zoom;
+
+        let mut stroke = stroke.into();
+        stroke.width *= zoom;
+        let width = stroke.width;
+
+        let corners = orthogonal_route(
+            (a_end.pos, a_end.side),
+            (b_end.pos, b_end.side),
+            ORTHOGONAL_STUB_LENGTH * zoom,
+        );
+        let points = round_corners(&corners, ORTHOGONAL_CORNER_RADIUS * zoom);
+
+        self.painter.add(Shape::line(points.clone(), stroke));
+
+        Some(self.interact_connection(a, b, &points, width))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Computes the corners of an orthogonal path from `a` to `b`, including its ends.
+///
+/// The path leaves each socket by at least `stub` pixels toward the side of its node.
+pub(super) fn orthogonal_route(
+    (a, a_side): (Pos2, NodeSide),
+    (b, b_side): (Pos2, NodeSide),
+    stub: f32,
+) -> Vec<Pos2> {
+    let a_dir = side_direction(a_side);
+    let b_dir = side_direction(b_side);
+
+    let a_out = a.x + a_dir * stub;
+    let b_out = b.x + b_dir * stub;
+
+    if a_side == b_side {
+        // Both sockets face the same way, go around the furthest one.
+        let x = if a_dir > 0.0 {
+            a_out.max(b_out)
+        } else {
+            a_out.min(b_out)
+        };
+
+        vec![a, pos2(x, a.y), pos2(x, b.y), b]
+    } else if (b_out - a_out) * a_dir >= 0.0 {
+        // `b` is in front of `a`, a single vertical segment is enough.
+        let x = (a.x + b.x) / 2.0;
+
+        vec![a, pos2(x, a.y), pos2(x, b.y), b]
+    } else {
+        // `b` is behind `a`, go back between the two sockets.
+        let y = (a.y + b.y) / 2.0;
+
+        vec![
+            a,
+            pos2(a_out, a.y),
+            pos2(a_out, y),
+            pos2(b_out, y),
+            pos2(b_out, b.y),
+            b,
+        ]
+    }
+}
+
+/// Replaces the inner `corners` of a path by arcs of `radius` pixels.
+///
+/// The radius is reduced when the segments around a corner are too short.
+pub(super) fn round_corners(corners: &[Pos2], radius: f32) -> Vec<Pos2> {
+    let mut points = Vec::with_capacity(corners.len() * usize::from(CORNER_SEGMENTS));
+
+    points.extend(corners.first());
+
+    for window in corners.windows(3) {
+        let [previous, corner, next] = window else {
+            continue;
+        };
+
+        let incoming = *corner - *previous;
+        let outgoing = *next - *corner;
+
+        let radius = radius
+            .min(incoming.length() / 2.0)
+            .min(outgoing.length() / 2.0);
+
+        if radius <= 0.0 {
+            points.push(*corner);
+            continue;
+        }
+
+        let start = *corner - incoming.normalized() * radius;
+        let end = *corner + outgoing.normalized() * radius;
+
+        // A quadratic bezier curve whose control point is the corner.
+        points.extend((0..=CORNER_SEGMENTS).map(|i| {
+            let t = f32::from(i) / f32::from(CORNER_SEGMENTS);
+            let start_weight = (1.0 - t) * (1.0 - t);
+            let corner_weight = 2.0 * (1.0 - t) * t;
+            let end_weight = t * t;
+
+            pos2(
+                start_weight * start.x + corner_weight * corner.x + end_weight * end.x,
+                start_weight * start.y + corner_weight * corner.y + end_weight * end.y,
+            )
+        }));
+    }
+
+    if corners.len() > 1 {
+        points.extend(corners.last());
+    }
+
+    points
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,810 @@
+//! Connections routed around the nodes.
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+};
+
+use egui::{epaint::PathStroke, pos2, Id, Pos2, Rect, Response, Shape};
+
+use crate::{editor::show::NodeGeometry, viewport::CanvasPos, NodeSide};
+
+use super::{
+    orthogonal::{
+        orthogonal_route, round_corners, ORTHOGONAL_CORNER_RADIUS, ORTHOGONAL_STUB_LENGTH,
+    },
+    side_direction, ConnectionsUi,
+};
+
+/* -------------------------------------------------------------------------- */
+
+/// The space kept free around the nodes, in canvas units.
+const ROUTING_MARGIN: f32 = 10.0;
+
+/// The extra cost of a bend in a route, in canvas units.
+const BEND_COST: f32 = 20.0;
+
+/// The margin around the ends of a route of the first area searched for the route, in canvas units.
+const SEARCH_MARGIN: f32 = 200.0;
+
+impl<S> ConnectionsUi<S>
+where
+    S: PartialEq + Clone + core::hash::Hash,
+{
+    /// Render the connection between two sockets with horizontal and vertical segments going around the nodes.
+    ///
+    /// The route avoids the nodes rendered during this frame and is only computed again when
+    /// a node or a socket moves. If there is no way around the nodes, the connection is rendered
+    /// like with [`Self::connect_orthogonal`].
+    ///
+    /// The stroke width is scaled by the [zoom factor](Self::zoom).
+    ///
+    /// Returns the [`Response`] of the connection, or `None` if a socket has not been rendered.
+    /// See [`Self::interact_connection`].
+    #[inline]
+    pub fn connect_routed(
+        &mut self,
+        a: &S,
+        b: &S,
+        stroke: impl Into<PathStroke>,
+    ) -> Option<Response> {
+        let (a_end, b_end) = self.endpoints(a, b)?;
+
+        let zoom = self.viewport.zoom;
+
+        let mut stroke = stroke.into();
+        stroke.width *= zoom;
+        let width = stroke.width;
+
+        let id = self.graph_id.with(("connection", a, b));
+        let to_canvas = |pos| self.viewport.viewport_to_canvas(pos).to_vec2().to_pos2();
+
+        let route = self.routes.route(
+            id,
+            (to_canvas(a_end.pos), a_end.side),
+            (to_canvas(b_end.pos), b_end.side),
+        );
+
+        let mut corners: Vec<Pos2> = route
+            .iter()
+            .map(|pos| {
+                self.viewport
+                    .canvas_to_viewport(CanvasPos::ZERO + pos.to_vec2())
+            })
+            .collect();
+
+        // The route has been computed with rounded ends, but its first and last segments are horizontal.
+        if let [first, second, ..] = corners.as_mut_slice() {
+            *first = a_end.pos;
+            second.y = a_end.pos.y;
+        }
+        if let [.., second_last, last] = corners.as_mut_slice() {
+            *last = b_end.pos;
+            second_last.y = b_end.pos.y;
+        }
+
+        let points = round_corners(&corners, ORTHOGONAL_CORNER_RADIUS * zoom);
+
+        self.painter.add(Shape::line(points.clone(), stroke));
+
+        Some(self.interact_connection(a, b, &points, width))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The connections routed around the nodes during the previous frames.
+///
+/// The routes are in canvas units and are reused until a node moves near them.
+#[derive(Clone, Default)]
+pub(in crate::editor) struct RouteCache {
+    /// The rects of the nodes the routes avoid, in canvas units.
+    obstacles: Vec<Rect>,
+    /// The routes of the connections, by connection id.
+    routes: HashMap<Id, Route>,
+}
+
+/// A connection routed around the nodes.
+#[derive(Clone)]
+struct Route {
+    /// The ends of the connection the route has been computed for.
+    ends: (Pos2, Pos2),
+    /// The corners of the route, including its ends.
+    corners: Vec<Pos2>,
+    /// Whether the connection has been rendered during this frame.
+    used: bool,
+}
+
+impl Route {
+    /// The area covered by the route, including its ends.
+    fn bounding_rect(&self) -> Rect {
+        Rect::from_points(&self.corners)
+            .union(Rect::from_two_pos(self.ends.0, self.ends.1))
+            .expand(ROUTING_MARGIN)
+    }
+}
+
+impl RouteCache {
+    /// Updates the obstacles with the nodes rendered during this frame.
+    ///
+    /// The routes near a node that has moved, resized, appeared or disappeared are dropped.
+    pub(in crate::editor) fn update(&mut self, geometries: &HashMap<Id, NodeGeometry>) {
+        let mut obstacles: Vec<Rect> = geometries
+            .values()
+            .map(|geometry| {
+                let min = geometry.canvas_pos().to_vec2().to_pos2();
+                let rect = Rect::from_min_size(min, geometry.canvas_size());
+
+                // Rounded to ignore the tiny variations, e.g. of the size of the text while zooming.
+                Rect::from_min_max(rect.min.floor(), rect.max.ceil())
+            })
+            .collect();
+
+        obstacles.sort_by(cmp_rects);
+
+        if obstacles == self.obstacles {
+            return;
+        }
+
+        // The rects of the nodes that have changed, both their old and new positions.
+        let changed: Vec<Rect> = obstacles
+            .iter()
+            .filter(|rect| {
+                self.obstacles
+                    .binary_search_by(|other| cmp_rects(other, rect))
+                    .is_err()
+            })
+            .chain(self.obstacles.iter().filter(|rect| {
+                obstacles
+                    .binary_search_by(|other| cmp_rects(other, rect))
+                    .is_err()
+            }))
+            .copied()
+            .collect();
+
+        self.routes.retain(|_, route| {
+            let area = route.bounding_rect();
+            !changed.iter().any(|rect| rect.intersects(area))
+        });
+
+        self.obstacles = obstacles;
+    }
+
+    /// Drops the routes of the connections that have not been rendered during this frame.
+    pub(in crate::editor) fn prune(&mut self) {
+        self.routes
+            .retain(|_, route| core::mem::replace(&mut route.used, false));
+    }
+
+    /// The corners of the route of the connection `id`, from `a` to `b`, in canvas units.
+    fn route(
+        &mut self,
+        id: Id,
+        (a, a_side): (Pos2, NodeSide),
+        (b, b_side): (Pos2, NodeSide),
+    ) -> &[Pos2] {
+        let ends = (a.round(), b.round());
+
+        if !self.routes.get(&id).is_some_and(|route| route.ends == ends) {
+            let corners = find_route(&self.obstacles, (ends.0, a_side), (ends.1, b_side));
+            self.routes.insert(
+                id,
+                Route {
+                    ends,
+                    corners,
+                    used: false,
+                },
+            );
+        }
+
+        self.routes.get_mut(&id).map_or(&[], |route| {
+            route.used = true;
+            route.corners.as_slice()
+        })
+    }
+}
+
+/// The order of the obstacles of a [`RouteCache`].
+fn cmp_rects(a: &Rect, b: &Rect) -> Ordering {
+    a.min
+        .x
+        .total_cmp(&b.min.x)
+        .then(a.min.y.total_cmp(&b.min.y))
+        .then(a.max.x.total_cmp(&b.max.x))
+        .then(a.max.y.total_cmp(&b.max.y))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Computes the corners of a route from `a` to `b` going around `obstacles`, including its ends.
+///
+/// Falls back to a route ignoring the obstacles if there is no way around them.
+fn find_route(
+    obstacles: &[Rect],
+    (a, a_side): (Pos2, NodeSide),
+    (b, b_side): (Pos2, NodeSide),
+) -> Vec<Pos2> {
+    let a_out = exit_point(obstacles, a, a_side);
+    let b_out = exit_point(obstacles, b, b_side);
+
+    let obstacles: Vec<Rect> = obstacles
+        .iter()
+        .map(|rect| rect.expand(ROUTING_MARGIN))
+        // The obstacles around the exit points can't be avoided, e.g. when nodes overlap.
+        .filter(|rect| !strictly_contains(rect, a_out) && !strictly_contains(rect, b_out))
+        .collect();
+
+    let start = (a_out, Direction::from_side(a_side));
+    let goal = (b_out, Direction::from_side(b_side).opposite());
+
+    // Only the obstacles around the ends are considered, the area is widened while there is no route.
+    let mut margin = SEARCH_MARGIN;
+    let path = loop {
+        let area = Rect::from_two_pos(a_out, b_out).expand(margin);
+
+        let nearby: Vec<Rect> = obstacles
+            .iter()
+            .filter(|rect| rect.intersects(area))
+            .copied()
+            .collect();
+
+        if nearby.len() == obstacles.len() {
+            // Leave room to go around all the obstacles.
+            let area = nearby
+                .iter()
+                .fold(area, |area, rect| area.union(*rect))
+                .expand(ROUTING_MARGIN);
+            break find_path(&nearby, area, start, goal);
+        }
+
+        if let Some(path) = find_path(&nearby, area, start, goal) {
+            break Some(path);
+        }
+
+        margin *= 4.0;
+    };
+
+    match path {
+        Some(path) => core::iter::once(a)
+            .chain(path)
+            .chain(core::iter::once(b))
+            .collect(),
+        None => orthogonal_route((a, a_side), (b, b_side), ORTHOGONAL_STUB_LENGTH),
+    }
+}
+
+/// The point where the route leaving the socket at `pos` clears the margin of its node.
+fn exit_point(obstacles: &[Rect], pos: Pos2, side: NodeSide) -> Pos2 {
+    let edges = obstacles
+        .iter()
+        .filter(|rect| rect.contains(pos))
+        .map(|rect| match side {
+            NodeSide::Left => rect.min.x - ROUTING_MARGIN,
+            NodeSide::Right => rect.max.x + ROUTING_MARGIN,
+        });
+
+    let x = match side {
+        NodeSide::Left => edges.reduce(f32::min),
+        NodeSide::Right => edges.reduce(f32::max),
+    };
+
+    pos2(
+        x.unwrap_or(pos.x + side_direction(side) * ORTHOGONAL_STUB_LENGTH),
+        pos.y,
+    )
+}
+
+/// Whether `pos` is inside `rect`, excluding its edges.
+fn strictly_contains(rect: &Rect, pos: Pos2) -> bool {
+    rect.min.x < pos.x && pos.x < rect.max.x && rect.min.y < pos.y && pos.y < rect.max.y
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A direction on the routing grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Direction {
+    /// Toward the smaller x.
+    Left,
+    /// Toward the bigger x.
+    Right,
+    /// Toward the smaller y.
+    Up,
+    /// Toward the bigger y.
+    Down,
+}
+
+impl Direction {
+    /// All the directions.
+    const ALL: [Direction; 4] = [
+        Direction::Left,
+        Direction::Right,
+        Direction::Up,
+        Direction::Down,
+    ];
+
+    /// The direction of a connection leaving a socket on `side`.
+    fn from_side(side: NodeSide) -> Direction {
+        match side {
+            NodeSide::Left => Direction::Left,
+            NodeSide::Right => Direction::Right,
+        }
+    }
+
+    /// The opposite direction.
+    fn opposite(self) -> Direction {
+        match self {
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+        }
+    }
+
+    /// The index of the next line of the grid in this direction.
+    fn step(self, (x, y): (usize, usize)) -> Option<(usize, usize)> {
+        match self {
+            Direction::Left => Some((x.checked_sub(1)?, y)),
+            Direction::Right => Some((x.checked_add(1)?, y)),
+            Direction::Up => Some((x, y.checked_sub(1)?)),
+            Direction::Down => Some((x, y.checked_add(1)?)),
+        }
+    }
+}
+
+/// A sparse grid made of the lines along the edges of the obstacles, of the area the route
+/// is searched in, and through the ends of the route.
+struct RoutingGrid {
+    /// The x of the vertical lines, sorted.
+    xs: Vec<f32>,
+    /// The y of the horizontal lines, sorted.
+    ys: Vec<f32>,
+    /// Whether the route can't go through the intersections of the grid and the middles between them.
+    ///
+    /// Indexed by [`RoutingGrid::half_index`].
+    blocked: Vec<bool>,
+}
+
+impl RoutingGrid {
+    /// Creates the grid around `obstacles` and going through `points`.
+    ///
+    /// The route can't leave `area`.
+    fn new(obstacles: &[Rect], area: Rect, points: [Pos2; 2]) -> Self {
+        let lines = |along: fn(Pos2) -> f32| {
+            let mut lines: Vec<f32> = obstacles
+                .iter()
+                .flat_map(|rect| [along(rect.min), along(rect.max)])
+                .chain([along(area.min), along(area.max)])
+                .chain(points.map(along))
+                .collect();
+            lines.sort_by(f32::total_cmp);
+            lines.dedup();
+            lines
+        };
+
+        let xs = lines(|pos| pos.x);
+        let ys = lines(|pos| pos.y);
+
+        // The coordinates of the lines and of the middles between them.
+        let halves = |lines: &[f32]| -> Vec<f32> {
+            lines
+                .iter()
+                .zip(lines.iter().skip(1).map(Some).chain([None]))
+                .flat_map(|(line, next)| {
+                    core::iter::once(*line).chain(next.map(|next| (line + next) / 2.0))
+                })
+                .collect()
+        };
+
+        let column_xs = halves(&xs);
+        let row_ys = halves(&ys);
+
+        let mut blocked: Vec<bool> = row_ys
+            .iter()
+            .flat_map(|y| {
+                column_xs
+                    .iter()
+                    .map(move |x| !(area.x_range().contains(*x) && area.y_range().contains(*y)))
+            })
+            .collect();
+
+        for rect in obstacles {
+            // The lines strictly inside the obstacle.
+            let columns = column_xs.partition_point(|x| *x <= rect.min.x)
+                ..column_xs.partition_point(|x| *x < rect.max.x);
+            let rows = row_ys.partition_point(|y| *y <= rect.min.y)
+                ..row_ys.partition_point(|y| *y < rect.max.y);
+
+            for row in rows {
+                let row_start = row * column_xs.len();
+                if let Some(cells) =
+                    blocked.get_mut(row_start + columns.start..row_start + columns.end)
+                {
+                    cells.fill(true);
+                }
+            }
+        }
+
+        RoutingGrid { xs, ys, blocked }
+    }
+
+    /// The index of the intersection of the grid at `pos`, if any.
+    fn index_of(&self, pos: Pos2) -> Option<(usize, usize)> {
+        let x = self.xs.binary_search_by(|x| x.total_cmp(&pos.x)).ok()?;
+        let y = self.ys.binary_search_by(|y| y.total_cmp(&pos.y)).ok()?;
+        Some((x, y))
+    }
+
+    /// The position of the intersection of the grid at `index`.
+    fn point(&self, (x, y): (usize, usize)) -> Option<Pos2> {
+        Some(pos2(*self.xs.get(x)?, *self.ys.get(y)?))
+    }
+
+    /// The index in [`Self::blocked`] of a point of the grid, whose coordinates are counted in half cells.
+    fn half_index(&self, (x, y): (usize, usize)) -> usize {
+        let columns = (2 * self.xs.len()).saturating_sub(1);
+        y * columns + x
+    }
+
+    /// Whether the route can go through the point of the grid at `half`, counted in half cells.
+    fn is_free(&self, half: (usize, usize)) -> bool {
+        self.blocked
+            .get(self.half_index(half))
+            .is_some_and(|blocked| !blocked)
+    }
+
+    /// Moves from the intersection at `index` in `direction`.
+    ///
+    /// Returns the index of the next intersection and the length of the move,
+    /// or `None` if the move goes through an obstacle or out of the grid.
+    fn step(&self, index: (usize, usize), direction: Direction) -> Option<((usize, usize), f32)> {
+        let next = direction.step(index)?;
+
+        let from = self.point(index)?;
+        let to = self.point(next)?;
+
+        let middle = (index.0 + next.0, index.1 + next.1);
+
+        (self.is_free((2 * next.0, 2 * next.1)) && self.is_free(middle))
+            .then(|| (next, from.distance(to)))
+    }
+}
+
+/// A state of the search: an intersection of the grid and the direction the route is going.
+type State = ((usize, usize), Direction);
+
+/// A state to explore, ordered by the estimated cost of the route going through it.
+struct Candidate {
+    /// The cost of the route to the state plus the estimated cost to the goal.
+    estimate: f32,
+    /// The cost of the route to the state.
+    cost: f32,
+    /// The state to explore.
+    state: State,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed, so the `BinaryHeap` gives the cheapest candidate first.
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+/// Finds the route from `start` to `goal` with the fewest bends among the shortest ones, using A*.
+///
+/// The route stays inside `area`, and starts going in `start_direction` and ends going in `goal_direction`.
+/// Returns its corners, including `start` and `goal`.
+fn find_path(
+    obstacles: &[Rect],
+    area: Rect,
+    (start, start_direction): (Pos2, Direction),
+    (goal, goal_direction): (Pos2, Direction),
+) -> Option<Vec<Pos2>> {
+    let grid = RoutingGrid::new(obstacles, area, [start, goal]);
+
+    let start = (grid.index_of(start)?, start_direction);
+    let goal_index = grid.index_of(goal)?;
+
+    let heuristic = |index| {
+        grid.point(index)
+            .map_or(0.0, |pos| (pos.x - goal.x).abs() + (pos.y - goal.y).abs())
+    };
+
+    let mut costs: HashMap<State, f32> = HashMap::from([(start, 0.0)]);
+    let mut previous: HashMap<State, State> = HashMap::new();
+    let mut open = BinaryHeap::from([Candidate {
+        estimate: heuristic(start.0),
+        cost: 0.0,
+        state: start,
+    }]);
+
+    while let Some(Candidate { cost, state, .. }) = open.pop() {
+        let (index, direction) = state;
+
+        if state == (goal_index, goal_direction) {
+            return Some(grid.corners(&previous, state));
+        }
+
+        if costs.get(&state).is_some_and(|best| *best < cost) {
+            // A cheaper route to this state has already been explored.
+            continue;
+        }
+
+        let moves = Direction::ALL
+            .into_iter()
+            .filter(|next_direction| *next_direction != direction.opposite())
+            .filter_map(|next_direction| {
+                let (next, length) = grid.step(index, next_direction)?;
+                let bend = if next_direction == direction {
+                    0.0
+                } else {
+                    BEND_COST
+                };
+                Some(((next, next_direction), length + bend))
+            });
+
+        // At the goal, the route can still turn toward the socket.
+        let turn = (index == goal_index && direction != goal_direction).then(|| {
+            let bends = if direction == goal_direction.opposite() {
+                2.0
+            } else {
+                1.0
+            };
+            ((index, goal_direction), bends * BEND_COST)
+        });
+
+        for (next, step_cost) in moves.chain(turn) {
+            let next_cost = cost + step_cost;
+
+            if costs.get(&next).map_or(true, |best| next_cost < *best) {
+                costs.insert(next, next_cost);
+                previous.insert(next, state);
+                open.push(Candidate {
+                    estimate: next_cost + heuristic(next.0),
+                    cost: next_cost,
+                    state: next,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+impl RoutingGrid {
+    /// The corners of the route found by [`find_path`] and ending at `state`.
+    fn corners(&self, previous: &HashMap<State, State>, mut state: State) -> Vec<Pos2> {
+        let mut path = vec![state.0];
+        while let Some(previous_state) = previous.get(&state) {
+            state = *previous_state;
+            path.push(state.0);
+        }
+        path.reverse();
+        path.dedup();
+
+        let mut corners: Vec<(usize, usize)> = Vec::with_capacity(path.len());
+
+        for index in path {
+            let is_aligned = matches!(
+                corners.as_slice(),
+                [.., a, b] if (a.0 == b.0 && b.0 == index.0) || (a.1 == b.1 && b.1 == index.1)
+            );
+
+            if is_aligned {
+                // The last corner is in the middle of a straight line.
+                corners.pop();
+            }
+
+            corners.push(index);
+        }
+
+        corners
+            .into_iter()
+            .filter_map(|index| self.point(index))
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+#[expect(clippy::expect_used, clippy::float_cmp, reason = "tests")]
+mod tests {
+    use super::*;
+
+    /// The area the routes are searched in.
+    fn area() -> Rect {
+        Rect::from_min_max(pos2(-500.0, -500.0), pos2(500.0, 500.0))
+    }
+
+    /// Asserts that `path` goes from `start` to `goal` with horizontal and vertical segments
+    /// that don't go through `obstacles`.
+    fn assert_valid_path(path: &[Pos2], obstacles: &[Rect], start: Pos2, goal: Pos2) {
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&goal));
+
+        for segment in path.windows(2) {
+            let [from, to] = segment else { continue };
+
+            assert!(from.x == to.x || from.y == to.y, "{from:?} -> {to:?}");
+
+            for i in 0..=10_u8 {
+                let pos = from.lerp(*to, f32::from(i) / 10.0);
+                assert!(
+                    !obstacles.iter().any(|rect| strictly_contains(rect, pos)),
+                    "{from:?} -> {to:?} goes through an obstacle"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn find_path_straight() {
+        let path = find_path(
+            &[],
+            area(),
+            (pos2(0.0, 0.0), Direction::Right),
+            (pos2(100.0, 0.0), Direction::Right),
+        );
+
+        assert_eq!(path, Some(vec![pos2(0.0, 0.0), pos2(100.0, 0.0)]));
+    }
+
+    #[test]
+    fn find_path_around_obstacle() {
+        let obstacles = [Rect::from_min_max(pos2(100.0, -50.0), pos2(200.0, 80.0))];
+        let start = pos2(0.0, 0.0);
+        let goal = pos2(300.0, 0.0);
+
+        let path = find_path(
+            &obstacles,
+            area(),
+            (start, Direction::Right),
+            (goal, Direction::Right),
+        )
+        .expect("a path around the obstacle");
+
+        assert_valid_path(&path, &obstacles, start, goal);
+        // Over the obstacle, which is shorter than under it.
+        assert!(path.iter().all(|pos| pos.y <= 0.0));
+        // At most four bends, the last one may be at the goal, and no corner in the middle of a straight line.
+        assert!(path.len() <= 6, "{path:?}");
+    }
+
+    #[test]
+    fn find_path_turns_toward_goal() {
+        let obstacles = [Rect::from_min_max(pos2(-10.0, -10.0), pos2(110.0, 60.0))];
+        let start = pos2(120.0, 25.0);
+        let goal = pos2(-20.0, 25.0);
+
+        // The route leaves on the right side and comes back to the left side of the same node.
+        let path = find_path(
+            &obstacles,
+            area(),
+            (start, Direction::Right),
+            (goal, Direction::Right),
+        )
+        .expect("a path around the obstacle");
+
+        assert_valid_path(&path, &obstacles, start, goal);
+        // The ends are outside of the node, so the route can turn right at them.
+        assert_eq!(path.len(), 4);
+    }
+
+    #[test]
+    fn find_path_unreachable() {
+        let obstacles = [Rect::from_min_max(pos2(100.0, -50.0), pos2(200.0, 50.0))];
+
+        let path = find_path(
+            &obstacles,
+            area(),
+            (pos2(0.0, 0.0), Direction::Right),
+            (pos2(150.0, 0.0), Direction::Right),
+        );
+
+        assert_eq!(path, None);
+    }
+
+    #[test]
+    fn find_path_stays_in_area() {
+        // The obstacle reaches the bottom of the area, the only way around is outside of it.
+        let obstacles = [Rect::from_min_max(pos2(100.0, -50.0), pos2(200.0, 150.0))];
+        let area = Rect::from_min_max(pos2(-50.0, 0.0), pos2(350.0, 100.0));
+
+        let path = find_path(
+            &obstacles,
+            area,
+            (pos2(0.0, 50.0), Direction::Right),
+            (pos2(300.0, 50.0), Direction::Right),
+        );
+
+        assert_eq!(path, None);
+    }
+
+    #[test]
+    fn find_route_widens_the_search() {
+        // A wall much taller than the first searched area.
+        let obstacles = [Rect::from_min_max(
+            pos2(100.0, -1000.0),
+            pos2(200.0, 1000.0),
+        )];
+        let a = pos2(0.0, 0.0);
+        let b = pos2(300.0, 0.0);
+
+        let route = find_route(&obstacles, (a, NodeSide::Right), (b, NodeSide::Left));
+
+        let margins = [obstacles[0].expand(ROUTING_MARGIN)];
+        assert_valid_path(&route, &margins, a, b);
+        assert!(route.iter().any(|pos| pos.y.abs() > 1000.0));
+    }
+
+    #[test]
+    fn corners_merge_straight_lines() {
+        // A grid with the lines 0, 10 and 20 in both directions.
+        let obstacles = [Rect::from_min_max(pos2(10.0, 10.0), pos2(10.0, 10.0))];
+        let area = Rect::from_min_max(pos2(0.0, 0.0), pos2(20.0, 20.0));
+        let grid = RoutingGrid::new(&obstacles, area, [area.min, area.max]);
+        assert_eq!(grid.xs, [0.0, 10.0, 20.0]);
+        assert_eq!(grid.ys, [0.0, 10.0, 20.0]);
+
+        let states = [
+            ((0, 0), Direction::Right),
+            ((1, 0), Direction::Right),
+            ((2, 0), Direction::Right),
+            // Turning doesn't move.
+            ((2, 0), Direction::Down),
+            ((2, 1), Direction::Down),
+            ((2, 2), Direction::Down),
+        ];
+        let previous: HashMap<State, State> = states
+            .windows(2)
+            .filter_map(|window| match window {
+                [previous, next] => Some((*next, *previous)),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(
+            grid.corners(&previous, ((2, 2), Direction::Down)),
+            [pos2(0.0, 0.0), pos2(20.0, 0.0), pos2(20.0, 20.0)]
+        );
+    }
+
+    #[test]
+    fn exit_point_clears_the_margin() {
+        let obstacles = [
+            Rect::from_min_max(pos2(0.0, 0.0), pos2(100.0, 50.0)),
+            Rect::from_min_max(pos2(50.0, 10.0), pos2(150.0, 40.0)),
+        ];
+
+        // Beyond all the overlapping nodes containing the socket.
+        assert_eq!(
+            exit_point(&obstacles, pos2(100.0, 25.0), NodeSide::Right),
+            pos2(150.0 + ROUTING_MARGIN, 25.0)
+        );
+        assert_eq!(
+            exit_point(&obstacles, pos2(0.0, 25.0), NodeSide::Left),
+            pos2(-ROUTING_MARGIN, 25.0)
+        );
+
+        // Outside of the nodes, the route leaves like an orthogonal connection.
+        assert_eq!(
+            exit_point(&obstacles, pos2(300.0, 25.0), NodeSide::Right),
+            pos2(300.0 + ORTHOGONAL_STUB_LENGTH, 25.0)
+        );
+    }
+}
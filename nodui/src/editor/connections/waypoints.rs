@@ -0,0 +1,218 @@
+//! Reroute points of the connections.
+
+use egui::{epaint::PathStroke, Pos2, Rect, Response, Vec2};
+
+use crate::{NodePos, Pos};
+
+use super::{bezier_curve, interact::distance_to_path, side_direction, ConnectionsUi};
+
+/* -------------------------------------------------------------------------- */
+
+/// An edit of the waypoints of a connection made by the user.
+///
+/// See [`ConnectionsUi::connect_bezier_through`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WaypointEdit<S> {
+    /// The user double-clicked the connection to insert a waypoint.
+    Insert {
+        /// The connection, as passed to [`ConnectionsUi::connect_bezier_through`].
+        connection: (S, S),
+        /// The index of the new waypoint.
+        index: usize,
+        /// The position of the new waypoint.
+        pos: Pos,
+    },
+    /// The user dragged a waypoint.
+    Move {
+        /// The connection, as passed to [`ConnectionsUi::connect_bezier_through`].
+        connection: (S, S),
+        /// The index of the moved waypoint.
+        index: usize,
+        /// The new position of the waypoint.
+        pos: Pos,
+    },
+    /// The user double-clicked a waypoint to remove it.
+    Remove {
+        /// The connection, as passed to [`ConnectionsUi::connect_bezier_through`].
+        connection: (S, S),
+        /// The index of the removed waypoint.
+        index: usize,
+    },
+}
+
+impl<S> WaypointEdit<S> {
+    /// The connection whose waypoints are edited.
+    #[inline]
+    pub fn connection(&self) -> &(S, S) {
+        match self {
+            WaypointEdit::Insert { connection, .. }
+            | WaypointEdit::Move { connection, .. }
+            | WaypointEdit::Remove { connection, .. } => connection,
+        }
+    }
+
+    /// Applies the edit to the waypoints of the connection.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn foo(graph: nodui::GraphResponse<u32>, waypoints: &mut Vec<nodui::Pos>) {
+    /// for edit in graph.waypoint_edits {
+    ///     // Find the waypoints of `edit.connection()`...
+    ///     edit.apply(waypoints);
+    /// }
+    /// # }
+    /// ```
+    #[inline]
+    pub fn apply(&self, waypoints: &mut Vec<Pos>) {
+        match *self {
+            WaypointEdit::Insert { index, pos, .. } => {
+                waypoints.insert(index.min(waypoints.len()), pos);
+            }
+            WaypointEdit::Move { index, pos, .. } => {
+                if let Some(waypoint) = waypoints.get_mut(index) {
+                    *waypoint = pos;
+                }
+            }
+            WaypointEdit::Remove { index, .. } => {
+                if index < waypoints.len() {
+                    waypoints.remove(index);
+                }
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The radius of the handle of a waypoint, in pixels at zoom `1.0`.
+const WAYPOINT_RADIUS: f32 = 4.0;
+
+impl<S> ConnectionsUi<S>
+where
+    S: PartialEq + Clone + core::hash::Hash,
+{
+    /// Render the connection between two sockets with bezier curves going through `waypoints`.
+    ///
+    /// The `waypoints` are ordered from `a` to `b`. The user can double-click the connection
+    /// to insert a waypoint, drag a waypoint to move it and double-click a waypoint to remove it.
+    /// The edits are reported in [`GraphResponse::waypoint_edits`](crate::GraphResponse::waypoint_edits),
+    /// the app is expected to apply them, see [`WaypointEdit::apply`].
+    ///
+    /// The stroke width is scaled by the [zoom factor](Self::zoom).
+    ///
+    /// Returns the [`Response`] of the connection, or `None` if a socket has not been rendered.
+    /// See [`Self::interact_connection`].
+    #[inline]
+    pub fn connect_bezier_through(
+        &mut self,
+        a: &S,
+        b: &S,
+        waypoints: &[Pos],
+        stroke: impl Into<PathStroke>,
+    ) -> Option<Response> {
+        let (a_end, b_end) = self.endpoints(a, b)?;
+
+        let mut stroke = stroke.into();
+        stroke.width *= self.viewport.zoom;
+        let width = stroke.width;
+
+        let anchors: Vec<Pos2> = core::iter::once(a_end.pos)
+            .chain(
+                waypoints
+                    .iter()
+                    .map(|pos| self.viewport.graph_to_viewport(*pos)),
+            )
+            .chain(core::iter::once(b_end.pos))
+            .collect();
+
+        // The horizontal direction of the connection at each anchor, going from `a` to `b`.
+        let directions: Vec<f32> = core::iter::once(side_direction(a_end.side))
+            .chain(anchors.windows(3).map(|anchors| match anchors {
+                [previous, _, next] if next.x < previous.x => -1.0,
+                _ => 1.0,
+            }))
+            .chain(core::iter::once(-side_direction(b_end.side)))
+            .collect();
+
+        let segments: Vec<Vec<Pos2>> = anchors
+            .windows(2)
+            .zip(directions.windows(2))
+            .filter_map(|segment| match segment {
+                ([from, to], [from_dir, to_dir]) => {
+                    let bezier = bezier_curve(*from, *from_dir, *to, -to_dir, stroke.clone());
+                    let points = bezier.flatten(None);
+                    self.painter.add(bezier);
+                    Some(points)
+                }
+                _ => None,
+            })
+            .collect();
+
+        let response = self.interact_connection(a, b, &segments.concat(), width);
+
+        if response.double_clicked() {
+            if let Some(pointer_pos) = response.interact_pointer_pos() {
+                // Insert the waypoint in the segment the closest to the pointer.
+                let index = segments
+                    .iter()
+                    .map(|points| distance_to_path(pointer_pos, points))
+                    .enumerate()
+                    .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                    .map_or(0, |(index, _)| index);
+
+                self.waypoint_edits.push(WaypointEdit::Insert {
+                    connection: (a.clone(), b.clone()),
+                    index,
+                    pos: self.pointer_to_pos(pointer_pos),
+                });
+            }
+        }
+
+        for (index, pos) in waypoints.iter().enumerate() {
+            self.waypoint_handle(a, b, index, *pos);
+        }
+
+        Some(response)
+    }
+
+    /// Renders and interacts with the handle of a waypoint.
+    fn waypoint_handle(&mut self, a: &S, b: &S, index: usize, pos: Pos) {
+        let center = self.viewport.graph_to_viewport(pos);
+        let radius = WAYPOINT_RADIUS * self.viewport.zoom;
+
+        let id = self.graph_id.with(("waypoint", a, b, index));
+        let rect = Rect::from_center_size(center, Vec2::splat(2.0 * radius));
+        let response = self.ui.interact(rect, id, egui::Sense::click_and_drag());
+
+        let visuals = self.ui.style().interact(&response);
+        self.painter
+            .circle(center, radius, visuals.bg_fill, visuals.fg_stroke);
+
+        let connection = (a.clone(), b.clone());
+
+        if response.double_clicked() {
+            self.waypoint_edits
+                .push(WaypointEdit::Remove { connection, index });
+        } else if response.dragged() {
+            let new_pos = response
+                .interact_pointer_pos()
+                .map(|pointer_pos| self.pointer_to_pos(pointer_pos));
+
+            if let Some(new_pos) = new_pos.filter(|new_pos| *new_pos != pos) {
+                self.waypoint_edits.push(WaypointEdit::Move {
+                    connection,
+                    index,
+                    pos: new_pos,
+                });
+            }
+        }
+    }
+
+    /// Converts the pointer position into the nearest graph position.
+    fn pointer_to_pos(&self, pointer_pos: Pos2) -> Pos {
+        Pos::from_free_pos(self.viewport.viewport_to_free_pos(pointer_pos))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
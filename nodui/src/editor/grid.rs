@@ -0,0 +1,266 @@
+//! Rendering of the editor's grid.
+
+use egui::{pos2, vec2, Color32, Rect, Shape, Stroke};
+
+use crate::viewport::Viewport;
+
+/* -------------------------------------------------------------------------- */
+
+/// The style of the grid of a [`GraphEditor`](crate::GraphEditor).
+///
+/// See [`GraphEditor::grid_style`](crate::GraphEditor::grid_style).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct GridStyle {
+    /// The shape used to render the grid.
+    pub shape: GridShape,
+    /// Emphasize every `n` cells with a major line, if any.
+    pub major_every: Option<u32>,
+    /// The color of the minor lines.
+    ///
+    /// Note: [`Color32::PLACEHOLDER`] will be replace by [`egui::Visuals::text_color`].
+    pub minor_color: Color32,
+    /// The color of the major lines.
+    ///
+    /// Note: [`Color32::PLACEHOLDER`] will be replace by [`egui::Visuals::strong_text_color`].
+    pub major_color: Color32,
+    /// The width of the minor lines.
+    pub minor_width: f32,
+    /// The width of the major lines.
+    pub major_width: f32,
+    /// The minimum space between two lines, in pixels.
+    ///
+    /// When the lines get closer than this value, some of them are hidden.
+    pub min_spacing: f32,
+}
+
+/// The shape used to render the grid.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum GridShape {
+    /// Continuous lines.
+    #[default]
+    Lines,
+    /// A dot at each intersection.
+    Dots,
+    /// A cross at each intersection.
+    Crosses,
+}
+
+impl Default for GridStyle {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            shape: GridShape::default(),
+            major_every: None,
+            minor_color: Color32::PLACEHOLDER,
+            major_color: Color32::PLACEHOLDER,
+            minor_width: 0.5,
+            major_width: 1.0,
+            min_spacing: 8.0,
+        }
+    }
+}
+
+impl GridStyle {
+    /// The shape used to render the grid.
+    #[must_use]
+    #[inline]
+    pub fn shape(mut self, shape: GridShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    /// Emphasize every `n` cells with a major line.
+    #[must_use]
+    #[inline]
+    pub fn major_every(mut self, n: u32) -> Self {
+        self.major_every = Some(n);
+        self
+    }
+
+    /// The color of the minor lines.
+    #[must_use]
+    #[inline]
+    pub fn minor_color(mut self, color: impl Into<Color32>) -> Self {
+        self.minor_color = color.into();
+        self
+    }
+
+    /// The color of the major lines.
+    #[must_use]
+    #[inline]
+    pub fn major_color(mut self, color: impl Into<Color32>) -> Self {
+        self.major_color = color.into();
+        self
+    }
+
+    /// The width of the minor lines.
+    #[must_use]
+    #[inline]
+    pub fn minor_width(mut self, width: f32) -> Self {
+        self.minor_width = width;
+        self
+    }
+
+    /// The width of the major lines.
+    #[must_use]
+    #[inline]
+    pub fn major_width(mut self, width: f32) -> Self {
+        self.major_width = width;
+        self
+    }
+
+    /// The minimum space between two lines, in pixels.
+    #[must_use]
+    #[inline]
+    pub fn min_spacing(mut self, min_spacing: f32) -> Self {
+        self.min_spacing = min_spacing;
+        self
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The maximum number of intersections rendered with [`GridShape::Dots`] or [`GridShape::Crosses`].
+const MAX_INTERSECTIONS: f32 = 10_000.0;
+
+/// A line of the grid, along one axis.
+#[derive(Clone, Copy)]
+struct GridLine {
+    /// The coordinate of the line in UI space.
+    coord: f32,
+    /// Whether this is a major line.
+    is_major: bool,
+}
+
+/// Show the editor grid.
+pub(super) fn paint(ui: &egui::Ui, rect: Rect, viewport: &Viewport, style: &GridStyle) {
+    let painter = ui.painter();
+    let visuals = ui.visuals();
+
+    let minor_color = if style.minor_color == Color32::PLACEHOLDER {
+        visuals.text_color()
+    } else {
+        style.minor_color
+    };
+
+    let major_color = if style.major_color == Color32::PLACEHOLDER {
+        visuals.strong_text_color()
+    } else {
+        style.major_color
+    };
+
+    let minor = Stroke::new(style.minor_width, minor_color);
+    let major = Stroke::new(style.major_width, major_color);
+
+    let major_every = style.major_every.filter(|n| *n > 1).map(i64::from);
+
+    let min_spacing = match style.shape {
+        GridShape::Lines => style.min_spacing.max(1.0),
+        // Each intersection is a shape, limit their number on large editors.
+        GridShape::Dots | GridShape::Crosses => style
+            .min_spacing
+            .max(1.0)
+            .max((rect.area() / MAX_INTERSECTIONS).sqrt()),
+    };
+
+    // Hide lines while they are too close to each other.
+    let cell_size = viewport.grid.size * viewport.zoom;
+    let mut step: i64 = 1;
+    #[allow(clippy::cast_precision_loss)]
+    while cell_size > 0.0 && cell_size * (step as f32) < min_spacing {
+        let Some(next_step) = step.checked_mul(major_every.unwrap_or(2)) else {
+            return;
+        };
+        step = next_step;
+    }
+    let major_every = major_every.and_then(|n| n.checked_mul(step));
+
+    #[allow(clippy::cast_precision_loss)]
+    let spacing = cell_size * step as f32;
+    let origin = viewport.position;
+
+    if spacing <= 0.0 || !spacing.is_finite() {
+        // The grid is degenerated, e.g. its cells have no size.
+        return;
+    }
+
+    let columns = grid_lines(rect.min.x, rect.max.x, origin.x, spacing, step, major_every);
+    let rows = grid_lines(rect.min.y, rect.max.y, origin.y, spacing, step, major_every);
+
+    let stroke_of = |is_major: bool| if is_major { major } else { minor };
+
+    match style.shape {
+        GridShape::Lines => {
+            for x in &columns {
+                painter.add(Shape::LineSegment {
+                    points: [pos2(x.coord, rect.min.y), pos2(x.coord, rect.max.y)],
+                    stroke: stroke_of(x.is_major),
+                });
+            }
+
+            for y in &rows {
+                painter.add(Shape::LineSegment {
+                    points: [pos2(rect.min.x, y.coord), pos2(rect.max.x, y.coord)],
+                    stroke: stroke_of(y.is_major),
+                });
+            }
+        }
+        GridShape::Dots => {
+            for x in &columns {
+                for y in &rows {
+                    let stroke = stroke_of(x.is_major && y.is_major);
+                    painter.circle_filled(pos2(x.coord, y.coord), stroke.width, stroke.color);
+                }
+            }
+        }
+        GridShape::Crosses => {
+            let arm = (spacing * 0.15).clamp(1.0, 6.0);
+
+            for x in &columns {
+                for y in &rows {
+                    let stroke = stroke_of(x.is_major && y.is_major);
+                    let center = pos2(x.coord, y.coord);
+
+                    painter
+                        .line_segment([center - vec2(arm, 0.0), center + vec2(arm, 0.0)], stroke);
+                    painter
+                        .line_segment([center - vec2(0.0, arm), center + vec2(0.0, arm)], stroke);
+                }
+            }
+        }
+    }
+}
+
+/// Computes the visible lines of the grid along one axis.
+///
+/// - `min` and `max` are the bounds of the visible area,
+/// - `origin` is the UI coordinate of the graph origin,
+/// - `spacing` is the distance between two visible lines,
+/// - `step` is the number of cells between two visible lines.
+fn grid_lines(
+    min: f32,
+    max: f32,
+    origin: f32,
+    spacing: f32,
+    step: i64,
+    major_every: Option<i64>,
+) -> Vec<GridLine> {
+    #[allow(clippy::cast_possible_truncation)]
+    let first = ((min - origin) / spacing).ceil() as i64;
+    #[allow(clippy::cast_possible_truncation)]
+    let last = ((max - origin) / spacing).floor() as i64;
+
+    (first..=last)
+        .map(|i| {
+            #[allow(clippy::cast_precision_loss)]
+            let coord = (i as f32).mul_add(spacing, origin);
+            let is_major = major_every.is_some_and(|n| (i * step) % n == 0);
+
+            GridLine { coord, is_major }
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,104 @@
+//! Keyboard controls of the editor.
+
+use egui::{Key, KeyboardShortcut, Modifiers, Vec2};
+
+/* -------------------------------------------------------------------------- */
+
+/// The keyboard controls of a [`GraphEditor`](crate::GraphEditor).
+///
+/// The controls are only active while the editor or one of its nodes has the focus.
+/// When a node has the focus, the arrow bindings move the node instead of the viewport.
+///
+/// Set a binding to `None` to disable it, e.g. to let the app use this shortcut.
+///
+/// See [`GraphEditor::keyboard`](crate::GraphEditor::keyboard).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyBindings {
+    /// Moves the viewport or the focused node to the left.
+    pub left: Option<KeyboardShortcut>,
+    /// Moves the viewport or the focused node to the right.
+    pub right: Option<KeyboardShortcut>,
+    /// Moves the viewport or the focused node up.
+    pub up: Option<KeyboardShortcut>,
+    /// Moves the viewport or the focused node down.
+    pub down: Option<KeyboardShortcut>,
+    /// Zooms in.
+    pub zoom_in: Option<KeyboardShortcut>,
+    /// Zooms out.
+    pub zoom_out: Option<KeyboardShortcut>,
+    /// Centers the viewport on the origin of the graph.
+    pub recenter: Option<KeyboardShortcut>,
+    /// Moves and zooms the viewport to make all the nodes visible.
+    pub fit_content: Option<KeyboardShortcut>,
+    /// The number of grid cells the viewport moves by when panning.
+    pub pan_step: f32,
+    /// The factor applied to the zoom when zooming in or out.
+    pub zoom_step: f32,
+}
+
+impl Default for KeyBindings {
+    #[inline]
+    fn default() -> Self {
+        let key = |key| Some(KeyboardShortcut::new(Modifiers::NONE, key));
+
+        Self {
+            left: key(Key::ArrowLeft),
+            right: key(Key::ArrowRight),
+            up: key(Key::ArrowUp),
+            down: key(Key::ArrowDown),
+            zoom_in: key(Key::Plus),
+            zoom_out: key(Key::Minus),
+            recenter: key(Key::Home),
+            fit_content: Some(KeyboardShortcut::new(Modifiers::SHIFT, Key::Home)),
+            pan_step: 5.0,
+            zoom_step: 1.25,
+        }
+    }
+}
+
+impl KeyBindings {
+    /// The number of grid cells the viewport moves by when panning.
+    #[must_use]
+    #[inline]
+    pub fn pan_step(mut self, pan_step: f32) -> Self {
+        self.pan_step = pan_step;
+        self
+    }
+
+    /// The factor applied to the zoom when zooming in or out.
+    #[must_use]
+    #[inline]
+    pub fn zoom_step(mut self, zoom_step: f32) -> Self {
+        self.zoom_step = zoom_step;
+        self
+    }
+
+    /// The direction of the arrow bindings pressed during this frame, in graph coordinates.
+    ///
+    /// The matching key events are consumed.
+    pub(super) fn consume_direction(&self, ui: &egui::Ui) -> Vec2 {
+        let mut direction = Vec2::ZERO;
+
+        if consume(ui, self.left) {
+            direction.x -= 1.0;
+        }
+        if consume(ui, self.right) {
+            direction.x += 1.0;
+        }
+        if consume(ui, self.up) {
+            direction.y += 1.0;
+        }
+        if consume(ui, self.down) {
+            direction.y -= 1.0;
+        }
+
+        direction
+    }
+}
+
+/// Consumes the shortcut, if any, and returns whether it was pressed.
+pub(super) fn consume(ui: &egui::Ui, shortcut: Option<KeyboardShortcut>) -> bool {
+    shortcut.is_some_and(|shortcut| ui.input_mut(|i| i.consume_shortcut(&shortcut)))
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,161 @@
+//! The minimap overlay of the editor.
+
+use egui::{epaint::RectShape, vec2, Align2, Color32, CornerRadius, Rect, StrokeKind, Vec2};
+
+use crate::viewport::{CanvasPos, Viewport};
+
+use super::EditorState;
+
+/* -------------------------------------------------------------------------- */
+
+/// The settings of the minimap of a [`GraphEditor`](crate::GraphEditor).
+///
+/// The minimap shows every rendered node and the area currently visible in the editor.
+/// Clicking or dragging in the minimap moves the viewport.
+#[derive(Debug, Clone, Copy)]
+pub struct Minimap {
+    /// The corner of the editor in which the minimap is shown.
+    pub corner: Align2,
+    /// The size of the minimap.
+    pub size: Vec2,
+}
+
+impl Default for Minimap {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            corner: Align2::RIGHT_BOTTOM,
+            size: vec2(200.0, 150.0),
+        }
+    }
+}
+
+impl Minimap {
+    /// The corner of the editor in which the minimap is shown.
+    #[must_use]
+    #[inline]
+    pub fn corner(mut self, corner: Align2) -> Self {
+        self.corner = corner;
+        self
+    }
+
+    /// The size of the minimap.
+    #[must_use]
+    #[inline]
+    pub fn size(mut self, size: impl Into<Vec2>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The space between the minimap and the border of the editor.
+const MINIMAP_MARGIN: f32 = 8.0;
+
+/// The space between the minimap border and its content.
+const MINIMAP_PADDING: f32 = 4.0;
+
+/// Render the minimap.
+///
+/// `editor_rect` and `node_rects` are in UI coordinates.
+///
+/// Returns the new viewport position if the user has interacted with the minimap.
+pub(super) fn show<S>(
+    ui: &mut egui::Ui,
+    id: egui::Id,
+    minimap: &Minimap,
+    editor_rect: Rect,
+    viewport: &Viewport,
+    node_rects: &[(egui::Id, Rect)],
+    state: &mut EditorState<S>,
+) -> Option<CanvasPos> {
+    let minimap_rect = minimap
+        .corner
+        .align_size_within_rect(minimap.size, editor_rect.shrink(MINIMAP_MARGIN));
+    let inner_rect = minimap_rect.shrink(MINIMAP_PADDING);
+
+    let layer_id = egui::LayerId::new(egui::Order::Foreground, id.with("minimap"));
+    let mut ui = ui.new_child(
+        egui::UiBuilder::new()
+            .layer_id(layer_id)
+            .max_rect(minimap_rect),
+    );
+    ui.set_clip_rect(minimap_rect.intersect(editor_rect));
+
+    let response = ui.interact(
+        minimap_rect,
+        id.with("minimap"),
+        egui::Sense::click_and_drag(),
+    );
+
+    /* ---- */
+
+    // The area of the graph shown by the minimap, in canvas units.
+    // It includes the visible area, so the user can always see where the viewport is.
+    let visible_area = viewport.viewport_to_canvas_rect(editor_rect);
+    let world = state.content_rect.map_or(visible_area, |content_rect| {
+        content_rect.union(visible_area)
+    });
+
+    // The area is kept while dragging, otherwise moving the viewport would also move the minimap content.
+    let world = if response.is_pointer_button_down_on() {
+        *state.minimap_world.get_or_insert(world)
+    } else {
+        state.minimap_world = None;
+        world
+    };
+
+    // The content is never shown bigger than in the editor.
+    let scale = (inner_rect.width() / world.size().x)
+        .min(inner_rect.height() / world.size().y)
+        .min(viewport.zoom);
+
+    let to_minimap =
+        |pos: CanvasPos| inner_rect.center() + (pos.to_vec2() - world.center().to_vec2()) * scale;
+    let from_minimap = |pos: egui::Pos2| world.center() + (pos - inner_rect.center()) / scale;
+
+    /* ---- */
+
+    let visuals = ui.visuals();
+    let painter = ui.painter();
+
+    painter.add(RectShape::new(
+        minimap_rect,
+        CornerRadius::same(2),
+        visuals.window_fill,
+        visuals.window_stroke,
+        StrokeKind::Inside,
+    ));
+
+    let node_color = visuals.widgets.inactive.bg_fill;
+    for (_, rect) in node_rects {
+        let rect = Rect::from_two_pos(
+            to_minimap(viewport.viewport_to_canvas(rect.min)),
+            to_minimap(viewport.viewport_to_canvas(rect.max)),
+        );
+        painter.add(RectShape::filled(rect, CornerRadius::ZERO, node_color));
+    }
+
+    let visible_rect = Rect::from_two_pos(
+        to_minimap(viewport.viewport_to_canvas(editor_rect.min)),
+        to_minimap(viewport.viewport_to_canvas(editor_rect.max)),
+    );
+    painter.with_clip_rect(inner_rect).add(RectShape::new(
+        visible_rect,
+        CornerRadius::ZERO,
+        Color32::TRANSPARENT,
+        visuals.selection.stroke,
+        StrokeKind::Inside,
+    ));
+
+    /* ---- */
+
+    if response.is_pointer_button_down_on() {
+        response.interact_pointer_pos().map(from_minimap)
+    } else {
+        None
+    }
+}
+
+/* -------------------------------------------------------------------------- */
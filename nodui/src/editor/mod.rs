@@ -1,6 +1,10 @@
 //! The graph editor.
 
+mod arrange;
 mod connections;
+mod grid;
+mod keyboard;
+mod minimap;
 mod response;
 mod show;
 pub mod stages;
@@ -10,16 +14,26 @@ use egui::Id;
 
 use crate::{
     misc::viewport::ViewportSize,
-    viewport::{CanvasPos, Grid, Viewport},
-    Pos, RenderedSocket,
+    viewport::{CanvasPos, CanvasRect, Grid, Viewport, ViewportState},
+    GraphRect, Pos, RenderedSocket, Snapping,
 };
 
-pub use connections::ConnectionsUi;
+pub use arrange::Arrangement;
+pub use connections::{ConnectionsUi, Flow, FlowPattern, WaypointEdit};
+pub use grid::{GridShape, GridStyle};
+pub use keyboard::KeyBindings;
+pub use minimap::Minimap;
 pub use response::GraphResponse;
 pub use show::{GraphUi, NodeResponse, NodeUi};
 
 use state::EditorState;
 
+/// The default minimum zoom factor, see [`GraphEditor::min_zoom`].
+const DEFAULT_MIN_ZOOM: f32 = 0.25;
+
+/// The default maximum zoom factor, see [`GraphEditor::max_zoom`].
+const DEFAULT_MAX_ZOOM: f32 = 4.0;
+
 /* -------------------------------------------------------------------------- */
 
 /// A node based graph editor.
@@ -50,7 +64,7 @@ pub struct GraphEditor<Stage> {
     stage: Stage,
 }
 
-impl GraphEditor<stages::Settings> {
+impl<'a, S> GraphEditor<stages::Settings<'a, S>> {
     /// Creates a new [`GraphEditor`].
     #[inline]
     pub fn new(id_salt: impl core::hash::Hash) -> Self {
@@ -58,9 +72,27 @@ impl GraphEditor<stages::Settings> {
             id: Id::new(id_salt),
             stage: stages::Settings {
                 show_grid: false,
-                look_at: None,
-                can_connect_socket: true,
+                grid_style: GridStyle::default(),
+                camera: stages::CameraSettings {
+                    restore: None,
+                    target: None,
+                    animation_time: None,
+                    auto_pan: true,
+                    min_zoom: DEFAULT_MIN_ZOOM,
+                    max_zoom: DEFAULT_MAX_ZOOM,
+                },
+                connection: stages::ConnectionSettings {
+                    enabled: true,
+                    can_connect: None,
+                    snap_radius: 0.0,
+                    rewire: false,
+                },
                 viewport: ViewportSize::default(),
+                minimap: None,
+                snapping: Snapping::default(),
+                key_bindings: None,
+                selection: None,
+                arrangement: None,
             },
         }
     }
@@ -69,7 +101,57 @@ impl GraphEditor<stages::Settings> {
     #[inline]
     #[must_use]
     pub fn look_at(mut self, pos: Pos) -> Self {
-        self.stage.look_at = Some(pos);
+        self.stage.camera.target = Some(stages::CameraTarget::LookAt(pos));
+        self
+    }
+
+    /// Restore the position and zoom factor of the viewport.
+    ///
+    /// See [`GraphResponse::viewport_state`].
+    #[inline]
+    #[must_use]
+    pub fn viewport_state(mut self, viewport_state: ViewportState) -> Self {
+        self.stage.camera.restore = Some(viewport_state);
+        self
+    }
+
+    /// Move and zoom the viewport to make all the nodes visible.
+    ///
+    /// The nodes rendered during the previous frame are used to compute the area to show.
+    #[inline]
+    #[must_use]
+    pub fn fit_all(mut self) -> Self {
+        self.stage.camera.target = Some(stages::CameraTarget::FitAll);
+        self
+    }
+
+    /// Move and zoom the viewport to make `rect` visible.
+    #[inline]
+    #[must_use]
+    pub fn fit_rect(mut self, rect: GraphRect) -> Self {
+        self.stage.camera.target = Some(stages::CameraTarget::FitRect(rect));
+        self
+    }
+
+    /// Animate the camera movements triggered by [`Self::look_at`], [`Self::fit_all`]
+    /// and [`Self::fit_rect`] over `time` seconds, instead of moving instantly.
+    ///
+    /// See [`GraphResponse::is_animating`].
+    #[inline]
+    #[must_use]
+    pub fn animate_camera(mut self, time: f32) -> Self {
+        self.stage.camera.animation_time = Some(time);
+        self
+    }
+
+    /// If `true` the viewport moves when the user drags a node or a connection
+    /// near the border of the editor.
+    ///
+    /// Default to `true`.
+    #[inline]
+    #[must_use]
+    pub fn auto_pan(mut self, auto_pan: bool) -> Self {
+        self.stage.camera.auto_pan = auto_pan;
         self
     }
 
@@ -83,6 +165,112 @@ impl GraphEditor<stages::Settings> {
         self
     }
 
+    /// The minimum zoom factor the user can reach.
+    ///
+    /// A limit that is not a finite positive number is ignored and the default one is used instead.
+    /// The limits are swapped if the minimum is greater than the [maximum](Self::max_zoom).
+    ///
+    /// Default to `0.25`.
+    #[inline]
+    #[must_use]
+    pub fn min_zoom(mut self, min_zoom: f32) -> Self {
+        self.stage.camera.min_zoom = min_zoom;
+        self
+    }
+
+    /// The maximum zoom factor the user can reach.
+    ///
+    /// A limit that is not a finite positive number is ignored and the default one is used instead.
+    /// The limits are swapped if the maximum is less than the [minimum](Self::min_zoom).
+    ///
+    /// Default to `4.0`.
+    #[inline]
+    #[must_use]
+    pub fn max_zoom(mut self, max_zoom: f32) -> Self {
+        self.stage.camera.max_zoom = max_zoom;
+        self
+    }
+
+    /// The style of the grid.
+    ///
+    /// See [`Self::show_grid`].
+    #[inline]
+    #[must_use]
+    pub fn grid_style(mut self, grid_style: GridStyle) -> Self {
+        self.stage.grid_style = grid_style;
+        self
+    }
+
+    /// Show a minimap in a corner of the editor.
+    ///
+    /// By default no minimap is shown.
+    #[inline]
+    #[must_use]
+    pub fn minimap(mut self, minimap: Minimap) -> Self {
+        self.stage.minimap = Some(minimap);
+        self
+    }
+
+    /// How the nodes are snapped when the user moves them.
+    ///
+    /// The snapping is applied while the node is dragged.
+    ///
+    /// Default to [`Snapping::Grid`].
+    #[inline]
+    #[must_use]
+    pub fn snapping(mut self, snapping: Snapping) -> Self {
+        self.stage.snapping = snapping;
+        self
+    }
+
+    /// Enable the keyboard controls.
+    ///
+    /// The controls are only active while the editor or one of its nodes has the focus.
+    /// Clicking on the editor gives it the focus.
+    ///
+    /// By default the keyboard controls are disabled.
+    #[inline]
+    #[must_use]
+    pub fn keyboard(mut self, key_bindings: KeyBindings) -> Self {
+        self.stage.key_bindings = Some(key_bindings);
+        self
+    }
+
+    /// Sets the selected nodes, replacing the current selection.
+    ///
+    /// `nodes` are the `id_salt` of the nodes, as passed to [`GraphUi::node`].
+    ///
+    /// See [`GraphResponse::is_selected`].
+    #[inline]
+    #[must_use]
+    pub fn select_nodes<T>(mut self, nodes: impl IntoIterator<Item = T>) -> Self
+    where
+        T: core::hash::Hash,
+    {
+        self.stage.selection = Some(nodes.into_iter().map(|node| self.id.with(node)).collect());
+        self
+    }
+
+    /// Aligns or distributes the selected nodes during this frame.
+    ///
+    /// The positions of the nodes are updated when they are rendered with [`GraphUi::node`],
+    /// using their sizes from the last frame. The nodes that were not rendered are left untouched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn foo(ui: &mut egui::Ui, arrangement: &mut Option<nodui::Arrangement>) {
+    /// let editor = nodui::GraphEditor::new("graph").arrange(arrangement.take());
+    /// # let _: nodui::GraphEditor<nodui::stages::Settings<'_, u32>> = editor;
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn arrange(mut self, arrangement: impl Into<Option<Arrangement>>) -> Self {
+        self.stage.arrangement = arrangement.into();
+        self
+    }
+
     /// If `true` the user can drag-n-drop a socket to create a connection.
     ///
     /// Can be useful to prevent user to edit the graph.
@@ -91,7 +279,62 @@ impl GraphEditor<stages::Settings> {
     #[inline]
     #[must_use]
     pub fn can_connect_socket(mut self, can_connect_socket: bool) -> Self {
-        self.stage.can_connect_socket = can_connect_socket;
+        self.stage.connection.enabled = can_connect_socket;
+        self
+    }
+
+    /// Sets which connections the user can create.
+    ///
+    /// `can_connect(source, target)` is called while the user drags a connection from `source`:
+    /// the sockets it rejects are greyed out, are never the [target](crate::ConnectionInProgress::target)
+    /// of the connection and dropping the connection on them does nothing.
+    ///
+    /// By default all the connections are allowed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn foo(editor: nodui::GraphEditor<nodui::stages::Settings<'_, (u32, usize)>>) {
+    /// // Only connect sockets from different nodes, sockets are identified by `(node, index)`.
+    /// let editor = editor.can_connect(|a, b| a.0 != b.0);
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn can_connect(mut self, can_connect: impl Fn(&S, &S) -> bool + 'a) -> Self {
+        self.stage.connection.can_connect = Some(Box::new(can_connect));
+        self
+    }
+
+    /// The distance from the pointer, in points, within which a dragged connection snaps
+    /// to the nearest socket it [can connect to](Self::can_connect).
+    ///
+    /// The snapped socket is the [target](crate::ConnectionInProgress::target) of the connection
+    /// and dropping the connection connects to it.
+    ///
+    /// Default to `0.0`, the pointer must be on a socket.
+    #[inline]
+    #[must_use]
+    pub fn connection_snap_radius(mut self, radius: f32) -> Self {
+        self.stage.connection.snap_radius = radius;
+        self
+    }
+
+    /// If `true` dragging a connected input socket (on the [left side](crate::NodeSide::Left))
+    /// picks up its connection: the connection is reported in [`GraphResponse::detached`]
+    /// and a new connection is dragged from its other end.
+    ///
+    /// The app is expected to remove the detached connection. Dropping it on a socket reports
+    /// a new connection as usual. Dropping it on the empty canvas reports its other end in
+    /// [`GraphResponse::dropped`], like any connection dragged from that socket.
+    ///
+    /// Only the connections rendered with the `connect_*` methods of [`ConnectionsUi`] can be picked up.
+    ///
+    /// Default to `false`.
+    #[inline]
+    #[must_use]
+    pub fn rewire_connections(mut self, rewire_connections: bool) -> Self {
+        self.stage.connection.rewire = rewire_connections;
         self
     }
 
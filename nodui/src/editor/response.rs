@@ -1,6 +1,8 @@
 //! Graph editor's response.
 
-use crate::{Pos, RenderedSocket, Viewport};
+use std::collections::HashSet;
+
+use crate::{GraphRect, Pos, RenderedSocket, Viewport, ViewportState, WaypointEdit};
 
 /* -------------------------------------------------------------------------- */
 
@@ -16,8 +18,75 @@ pub struct GraphResponse<S> {
     pub sockets: Vec<RenderedSocket<S>>,
     /// Whether the user create a new connection.
     pub connection: Option<(S, S)>,
+    /// The connection the user has picked up from one of its sockets, if any.
+    ///
+    /// See [`GraphEditor::rewire_connections`][crate::GraphEditor::rewire_connections].
+    pub detached: Option<(S, S)>,
+    /// The socket from which the user has dropped a connection on the empty canvas, and the
+    /// graph position where it was dropped, if any.
+    ///
+    /// E.g. the app can open a menu there to create a node and connect it to the socket.
+    pub dropped: Option<(S, Pos)>,
+    /// The connections the user has cut with the knife during this frame.
+    ///
+    /// The knife is drawn by dragging the canvas with the secondary button while holding <kbd>Ctrl</kbd>.
+    /// Only the connections hit-tested with [`ConnectionsUi::interact_connection`][crate::ConnectionsUi::interact_connection]
+    /// can be cut, which the `connect_*` methods already do.
+    pub cut: Vec<(S, S)>,
+    /// The edits of the waypoints of the connections made by the user during this frame.
+    ///
+    /// See [`ConnectionsUi::connect_bezier_through`][crate::ConnectionsUi::connect_bezier_through].
+    pub waypoint_edits: Vec<WaypointEdit<S>>,
     /// The position of the viewport.
     pub position: Pos,
+    /// The zoom factor of the viewport.
+    pub zoom: f32,
+    /// The position and zoom factor of the viewport.
+    ///
+    /// Can be saved and later restored with [`GraphEditor::viewport_state`][crate::GraphEditor::viewport_state].
+    pub viewport_state: ViewportState,
+    /// The UI rect that contains all the rendered nodes, if any.
+    pub nodes_rect: Option<egui::Rect>,
+    /// Whether the camera is still moving toward its target.
+    ///
+    /// See [`GraphEditor::animate_camera`][crate::GraphEditor::animate_camera].
+    pub is_animating: bool,
+    /// The ids of the selected nodes.
+    ///
+    /// See [`Self::node_id`] and [`Self::is_selected`].
+    pub selection: HashSet<egui::Id>,
+    /// Whether the user has changed the selection during this frame.
+    pub selection_changed: bool,
+}
+
+impl<S> GraphResponse<S> {
+    /// The smallest graph rect that contains all the rendered nodes, if any.
+    ///
+    /// Can be used with [`GraphEditor::fit_rect`][crate::GraphEditor::fit_rect].
+    #[inline]
+    #[must_use]
+    pub fn content_rect(&self) -> Option<GraphRect> {
+        self.nodes_rect
+            .map(|rect| self.viewport.viewport_to_graph_rect(rect))
+    }
+
+    /// The id of a node, as used in [`Self::selection`].
+    ///
+    /// `id_salt` is the one passed to [`GraphUi::node`][crate::GraphUi::node].
+    #[inline]
+    #[must_use]
+    pub fn node_id(&self, id_salt: impl core::hash::Hash) -> egui::Id {
+        self.response.id.with(id_salt)
+    }
+
+    /// Whether a node is selected.
+    ///
+    /// `id_salt` is the one passed to [`GraphUi::node`][crate::GraphUi::node].
+    #[inline]
+    #[must_use]
+    pub fn is_selected(&self, id_salt: impl core::hash::Hash) -> bool {
+        self.selection.contains(&self.node_id(id_salt))
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,365 @@
+//! The user interactions with the camera of the editor.
+
+use std::ops::RangeInclusive;
+
+use crate::{
+    editor::{
+        keyboard::{self, KeyBindings},
+        stages::{CameraSettings, CameraTarget},
+        state::{CameraAnimation, DragPhase, EditorState},
+    },
+    viewport::ViewportState,
+    Pos, Viewport,
+};
+
+/* -------------------------------------------------------------------------- */
+
+/// Updates the camera of the editor and returns the resulting [`Viewport`].
+pub(super) fn update<S>(
+    ui: &egui::Ui,
+    response: &egui::Response,
+    state: &mut EditorState<S>,
+    settings: &CameraSettings,
+    key_bindings: Option<&KeyBindings>,
+) -> Viewport {
+    let CameraSettings {
+        restore,
+        target,
+        animation_time,
+        auto_pan,
+        min_zoom,
+        max_zoom,
+    } = *settings;
+
+    let rect = response.rect;
+    let zoom_range = zoom_range(min_zoom, max_zoom);
+
+    if let Some(restore) = restore {
+        state.camera = ViewportState {
+            // `max` first, so a NaN zoom factor is replaced by the minimum.
+            zoom: restore.zoom.max(*zoom_range.start()).min(*zoom_range.end()),
+            ..restore
+        };
+        state.camera_animation = None;
+    }
+
+    handle_input(ui, response, state, zoom_range.clone());
+
+    if auto_pan {
+        self::auto_pan(ui, rect, state);
+    }
+
+    let keyboard_target = key_bindings.and_then(|key_bindings| {
+        handle_keyboard(ui, response, state, key_bindings, zoom_range.clone())
+    });
+
+    if let Some(target) = keyboard_target.or(target) {
+        if let Some(target) = target_state(state, rect, target, zoom_range) {
+            move_to(ui, state, target, animation_time);
+        }
+    }
+
+    animate(ui, state);
+
+    Viewport {
+        position: rect.center().to_vec2() - state.camera.position.to_vec2() * state.camera.zoom,
+        zoom: state.camera.zoom,
+        grid: state.grid.clone(),
+    }
+}
+
+/// The range of the zoom factor, from the limits set by the user.
+///
+/// The limits that are not finite positive numbers are replaced by the default ones,
+/// and they are swapped if the minimum is greater than the maximum.
+fn zoom_range(min_zoom: f32, max_zoom: f32) -> RangeInclusive<f32> {
+    let is_valid = |zoom: f32| zoom.is_finite() && zoom > 0.0;
+
+    let min_zoom = if is_valid(min_zoom) {
+        min_zoom
+    } else {
+        crate::editor::DEFAULT_MIN_ZOOM
+    };
+    let max_zoom = if is_valid(max_zoom) {
+        max_zoom
+    } else {
+        crate::editor::DEFAULT_MAX_ZOOM
+    };
+
+    min_zoom.min(max_zoom)..=min_zoom.max(max_zoom)
+}
+
+/// Moves and zooms the viewport based on the user inputs.
+fn handle_input<S>(
+    ui: &egui::Ui,
+    response: &egui::Response,
+    state: &mut EditorState<S>,
+    zoom_range: RangeInclusive<f32>,
+) {
+    let rect = response.rect;
+
+    // The drag is used by the marquee selection or the knife.
+    if response.dragged() && state.marquee.is_none() && state.knife.is_none() {
+        response.ctx.set_cursor_icon(egui::CursorIcon::Grabbing);
+        state.camera.position -= response.drag_delta() / state.camera.zoom;
+        state.camera_animation = None;
+    }
+
+    // Zoom with ctrl+scroll or pinch gesture, keeping the point under the pointer in place.
+    // The windows and popups over the editor get the scroll instead. Unlike `response.contains_pointer()`,
+    // this also holds over the nodes, which are on their own layers.
+    if let Some(pointer_pos) = ui.input(|i| i.pointer.hover_pos()) {
+        if ui.rect_contains_pointer(rect) {
+            let zoom_delta = ui.input(egui::InputState::zoom_delta);
+            if (zoom_delta - 1.0).abs() > f32::EPSILON {
+                state.camera_animation = None;
+            }
+            zoom_at(
+                state,
+                rect,
+                pointer_pos,
+                state.camera.zoom * zoom_delta,
+                &zoom_range,
+            );
+        }
+    }
+
+    // Ensure the zoom respects the limits even if they changed since the last frame.
+    state.camera.zoom = state
+        .camera
+        .zoom
+        .clamp(*zoom_range.start(), *zoom_range.end());
+}
+
+/// Prevents egui from moving the focus away from the widget `id` with the arrow keys.
+pub(super) fn lock_arrows_focus(ui: &egui::Ui, id: egui::Id) {
+    ui.memory_mut(|mem| {
+        mem.set_focus_lock_filter(
+            id,
+            egui::EventFilter {
+                horizontal_arrows: true,
+                vertical_arrows: true,
+                ..Default::default()
+            },
+        );
+    });
+}
+
+/// Moves and zooms the viewport based on the keyboard inputs.
+///
+/// Returns the target the viewport should move to, if any.
+fn handle_keyboard<S>(
+    ui: &egui::Ui,
+    response: &egui::Response,
+    state: &mut EditorState<S>,
+    key_bindings: &KeyBindings,
+    zoom_range: RangeInclusive<f32>,
+) -> Option<CameraTarget> {
+    if response.clicked() || response.drag_started() {
+        response.request_focus();
+    }
+
+    // Keep the focus on the editor when the arrows are pressed.
+    lock_arrows_focus(ui, response.id);
+
+    let node_has_focus = state
+        .focused_node
+        .is_some_and(|id| ui.memory(|mem| mem.has_focus(id)));
+
+    if !response.has_focus() && !node_has_focus {
+        return None;
+    }
+
+    // The arrows move the focused node instead of the viewport.
+    if response.has_focus() {
+        let direction = key_bindings.consume_direction(ui);
+        if direction != egui::Vec2::ZERO {
+            state.camera.position +=
+                egui::vec2(direction.x, -direction.y) * key_bindings.pan_step * state.grid.size;
+            state.camera_animation = None;
+        }
+    }
+
+    let rect = response.rect;
+
+    if keyboard::consume(ui, key_bindings.zoom_in) {
+        let zoom = state.camera.zoom * key_bindings.zoom_step;
+        zoom_at(state, rect, rect.center(), zoom, &zoom_range);
+        state.camera_animation = None;
+    }
+
+    if keyboard::consume(ui, key_bindings.zoom_out) {
+        let zoom = state.camera.zoom / key_bindings.zoom_step;
+        zoom_at(state, rect, rect.center(), zoom, &zoom_range);
+        state.camera_animation = None;
+    }
+
+    // Check the most specific shortcut first, since extra modifiers are ignored.
+    if keyboard::consume(ui, key_bindings.fit_content) {
+        Some(CameraTarget::FitAll)
+    } else if keyboard::consume(ui, key_bindings.recenter) {
+        Some(CameraTarget::LookAt(Pos::new(0, 0)))
+    } else {
+        None
+    }
+}
+
+/// The distance from the editor's border, in pixels, at which the auto-pan starts.
+const AUTO_PAN_MARGIN: f32 = 32.0;
+
+/// The maximum speed of the auto-pan, in pixels per second.
+const AUTO_PAN_SPEED: f32 = 800.0;
+
+/// Moves the viewport while a node or a connection is dragged near the border of the editor.
+///
+/// The closer the pointer is to the border, the faster the viewport moves.
+fn auto_pan<S>(ui: &egui::Ui, rect: egui::Rect, state: &mut EditorState<S>) {
+    let is_dragging_nodes = state
+        .node_drag
+        .as_ref()
+        .is_some_and(|drag| drag.phase == DragPhase::Dragging);
+
+    if !is_dragging_nodes && state.socket_drag.is_none() {
+        return;
+    }
+
+    let Some(pointer_pos) = ui.input(|i| i.pointer.latest_pos()) else {
+        return;
+    };
+
+    // How much the pointer goes into the margin, from 0 to 1.
+    let strength = |min: f32, max: f32, pos: f32| {
+        if pos < min + AUTO_PAN_MARGIN {
+            -((min + AUTO_PAN_MARGIN - pos) / AUTO_PAN_MARGIN).min(1.0)
+        } else if pos > max - AUTO_PAN_MARGIN {
+            ((pos - max + AUTO_PAN_MARGIN) / AUTO_PAN_MARGIN).min(1.0)
+        } else {
+            0.0
+        }
+    };
+
+    let direction = egui::vec2(
+        strength(rect.min.x, rect.max.x, pointer_pos.x),
+        strength(rect.min.y, rect.max.y, pointer_pos.y),
+    );
+
+    if direction == egui::Vec2::ZERO {
+        return;
+    }
+
+    let dt = ui.input(|i| i.stable_dt).min(0.1);
+    let delta = direction * AUTO_PAN_SPEED * dt / state.camera.zoom;
+
+    state.camera.position += delta;
+    state.camera_animation = None;
+
+    // Keep the dragged nodes under the pointer.
+    if let Some(drag) = state.node_drag.as_mut() {
+        drag.delta += delta;
+    }
+
+    ui.ctx().request_repaint();
+}
+
+/// The space kept around the content when fitting it in the viewport.
+const FIT_MARGIN: f32 = 32.0;
+
+/// Computes the state of the viewport that shows the target.
+fn target_state<S>(
+    state: &EditorState<S>,
+    rect: egui::Rect,
+    target: CameraTarget,
+    zoom_range: RangeInclusive<f32>,
+) -> Option<ViewportState> {
+    let fit_rect = match target {
+        CameraTarget::LookAt(pos) => {
+            return Some(ViewportState {
+                position: state.grid.graph_to_canvas(pos),
+                zoom: state.camera.zoom,
+            });
+        }
+        CameraTarget::FitAll => state.content_rect,
+        CameraTarget::FitRect(graph_rect) => Some(state.grid.graph_rect_to_canvas(graph_rect)),
+    }?;
+
+    let available_size =
+        (rect.size() - egui::Vec2::splat(2.0 * FIT_MARGIN)).max(egui::Vec2::splat(1.0));
+    let content_size = fit_rect.size().max(egui::Vec2::splat(1.0));
+
+    let zoom = (available_size.x / content_size.x).min(available_size.y / content_size.y);
+
+    Some(ViewportState {
+        position: fit_rect.center(),
+        zoom: zoom.clamp(*zoom_range.start(), *zoom_range.end()),
+    })
+}
+
+/// Moves the viewport to `target`, either instantly or with an animation.
+fn move_to<S>(
+    ui: &egui::Ui,
+    state: &mut EditorState<S>,
+    target: ViewportState,
+    animation_time: Option<f32>,
+) {
+    match animation_time {
+        Some(duration) if duration > 0.0 => {
+            // Don't restart the animation if the target is requested again on each frame.
+            let already_there = match state.camera_animation {
+                Some(animation) => animation.to == target,
+                None => state.camera == target,
+            };
+
+            if !already_there {
+                state.camera_animation = Some(CameraAnimation {
+                    from: state.camera,
+                    to: target,
+                    start_time: ui.input(|i| i.time),
+                    duration,
+                });
+            }
+        }
+        _ => {
+            state.camera = target;
+            state.camera_animation = None;
+        }
+    }
+}
+
+/// Advances the running animation of the camera, if any.
+fn animate<S>(ui: &egui::Ui, state: &mut EditorState<S>) {
+    let Some(animation) = state.camera_animation else {
+        return;
+    };
+
+    let elapsed = ui.input(|i| i.time) - animation.start_time;
+    #[allow(clippy::cast_possible_truncation)]
+    let t = (elapsed as f32 / animation.duration).clamp(0.0, 1.0);
+
+    state.camera = animation
+        .from
+        .lerp(animation.to, egui::emath::easing::cubic_in_out(t));
+
+    if t < 1.0 {
+        ui.ctx().request_repaint();
+    } else {
+        state.camera = animation.to;
+        state.camera_animation = None;
+    }
+}
+
+/// Changes the zoom factor while keeping the point at `anchor` in place.
+fn zoom_at<S>(
+    state: &mut EditorState<S>,
+    rect: egui::Rect,
+    anchor: egui::Pos2,
+    zoom: f32,
+    zoom_range: &RangeInclusive<f32>,
+) {
+    let new_zoom = zoom.clamp(*zoom_range.start(), *zoom_range.end());
+
+    let offset = anchor - rect.center();
+    state.camera.position += offset / state.camera.zoom - offset / new_zoom;
+    state.camera.zoom = new_zoom;
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,131 @@
+//! Culling of the nodes outside of the visible area.
+
+use egui::{Color32, Pos2, Rect, Vec2};
+
+use crate::{misc::collector::Collector, viewport::CanvasPos, RenderedSocket, Socket};
+
+/* -------------------------------------------------------------------------- */
+
+/// The geometry of a node, saved from the last frame it was fully rendered.
+///
+/// All values are in canvas units, relative to the position of the node.
+#[derive(Clone)]
+pub(in crate::editor) struct NodeGeometry {
+    /// The position of the node.
+    pos: CanvasPos,
+    /// The size of the node.
+    size: Vec2,
+    /// The rect of the handle of each socket, in the order they were added.
+    sockets: Vec<Rect>,
+}
+
+impl NodeGeometry {
+    /// Saves the geometry of a node that has been rendered at `canvas_pos`, or `pos` in UI coordinates.
+    pub(super) fn new<S>(
+        canvas_pos: CanvasPos,
+        pos: Pos2,
+        size: Vec2,
+        sockets: &[RenderedSocket<S>],
+        zoom: f32,
+    ) -> Self {
+        let sockets = sockets
+            .iter()
+            .map(|socket| {
+                let rect = socket.response.rect;
+                Rect::from_min_size(((rect.min - pos) / zoom).to_pos2(), rect.size() / zoom)
+            })
+            .collect();
+
+        NodeGeometry {
+            pos: canvas_pos,
+            size: size / zoom,
+            sockets,
+        }
+    }
+
+    /// The size of the node, in UI units.
+    pub(super) fn size(&self, zoom: f32) -> Vec2 {
+        self.size * zoom
+    }
+
+    /// The position of the node, in canvas units.
+    pub(in crate::editor) fn canvas_pos(&self) -> CanvasPos {
+        self.pos
+    }
+
+    /// The size of the node, in canvas units.
+    pub(in crate::editor) fn canvas_size(&self) -> Vec2 {
+        self.size
+    }
+
+    /// Moves the saved geometry to `pos`.
+    pub(super) fn moved_to(self, pos: CanvasPos) -> Self {
+        NodeGeometry { pos, ..self }
+    }
+
+    /// The UI rect of the handle of each socket.
+    fn socket_rects(&self, pos: Pos2, zoom: f32) -> impl Iterator<Item = Rect> + '_ {
+        self.sockets.iter().map(move |rect| {
+            Rect::from_min_size(pos + rect.min.to_vec2() * zoom, rect.size() * zoom)
+        })
+    }
+
+    /// Whether a node rendered at `pos` with `sockets` would be outside of `clip_rect`.
+    pub(super) fn is_culled<S>(
+        &self,
+        clip_rect: Rect,
+        pos: Pos2,
+        zoom: f32,
+        sockets: &[Socket<S>],
+    ) -> bool {
+        // The sockets have changed since the last time the node was rendered.
+        if sockets.len() != self.sockets.len() {
+            return false;
+        }
+
+        let bounds = self
+            .socket_rects(pos, zoom)
+            .fold(Rect::from_min_size(pos, self.size(zoom)), Rect::union);
+
+        !clip_rect.intersects(bounds)
+    }
+
+    /// Registers the sockets of a culled node without doing any layout or painting.
+    ///
+    /// The sockets keep the same ids as when the node is fully rendered,
+    /// so on-going interactions are not lost.
+    pub(super) fn show_culled<S>(
+        &self,
+        ui: &egui::Ui,
+        pos: Pos2,
+        zoom: f32,
+        sockets: Vec<Socket<S>>,
+        rendered_sockets: &mut Collector<RenderedSocket<S>>,
+    ) where
+        S: core::hash::Hash,
+    {
+        for (socket, rect) in sockets.into_iter().zip(self.socket_rects(pos, zoom)) {
+            let Socket {
+                id,
+                side,
+                mut color,
+                ..
+            } = socket;
+
+            if color == Color32::PLACEHOLDER {
+                color = ui.visuals().strong_text_color();
+            }
+
+            let response = ui.interact(rect, ui.id().with(&id), egui::Sense::click_and_drag());
+
+            rendered_sockets.push(RenderedSocket {
+                id,
+                response,
+                side,
+                color,
+            });
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
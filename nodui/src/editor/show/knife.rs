@@ -0,0 +1,98 @@
+//! Cutting of the connections with a knife.
+
+use egui::{Pos2, Rect, Shape, Stroke};
+
+use crate::editor::state::EditorState;
+
+use super::Viewport;
+
+/* -------------------------------------------------------------------------- */
+
+/// Starts a knife cut when the user drags the canvas with the secondary button
+/// while holding <kbd>Ctrl</kbd>.
+///
+/// Must be called before the camera is updated so the drag doesn't move the viewport.
+pub(super) fn begin<S>(ui: &egui::Ui, response: &egui::Response, state: &mut EditorState<S>) {
+    if !response.drag_started_by(egui::PointerButton::Secondary)
+        || !ui.input(|i| i.modifiers.command)
+    {
+        return;
+    }
+
+    if let Some(press_origin) = ui.input(|i| i.pointer.press_origin()) {
+        let offset = press_origin - response.rect.center();
+        state.knife = Some(vec![state.camera.position + offset / state.camera.zoom]);
+    }
+}
+
+/// The minimum distance between two points of the knife, in pixels.
+const KNIFE_MIN_DISTANCE: f32 = 4.0;
+
+/// Updates the on-going knife cut.
+///
+/// Returns the cut line when the user releases it.
+pub(super) fn update<S>(
+    ui: &egui::Ui,
+    response: &egui::Response,
+    viewport: &Viewport,
+    state: &mut EditorState<S>,
+) -> Option<KnifeLine> {
+    let knife = state.knife.as_mut()?;
+
+    if let Some(pointer_pos) = ui.input(|i| i.pointer.latest_pos()) {
+        let pointer_pos = viewport.viewport_to_canvas(pointer_pos);
+        let is_too_close = knife.last().is_some_and(|last| {
+            (pointer_pos.to_vec2() - last.to_vec2()).length() * viewport.zoom < KNIFE_MIN_DISTANCE
+        });
+        if !is_too_close {
+            knife.push(pointer_pos);
+        }
+    }
+
+    let points: Vec<Pos2> = knife
+        .iter()
+        .map(|pos| viewport.canvas_to_viewport(*pos))
+        .collect();
+
+    if !response.dragged() {
+        // The knife has been released.
+        state.knife = None;
+        return Some(KnifeLine(points));
+    }
+
+    ui.ctx().set_cursor_icon(egui::CursorIcon::Crosshair);
+
+    let layer_id = egui::LayerId::new(egui::Order::Foreground, response.id.with("knife"));
+    let stroke = Stroke::new(1.5, ui.visuals().error_fg_color);
+    ui.painter()
+        .clone()
+        .with_layer_id(layer_id)
+        .extend(Shape::dashed_line(&points, stroke, 6.0, 4.0));
+
+    None
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The line drawn by the user to cut the connections, in UI coordinates.
+pub(in crate::editor) struct KnifeLine(Vec<Pos2>);
+
+impl KnifeLine {
+    /// Whether the knife crosses the path `points`.
+    pub(in crate::editor) fn crosses(&self, points: &[Pos2]) -> bool {
+        let Self(knife) = self;
+
+        if !Rect::from_points(knife).intersects(Rect::from_points(points)) {
+            return false;
+        }
+
+        knife.windows(2).any(|knife| {
+            points.windows(2).any(|path| match (knife, path) {
+                ([a, b], [c, d]) => super::selection::segments_intersect(*a, *b, *c, *d),
+                _ => false,
+            })
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -1,16 +1,26 @@
 //! The rendering of the editor's viewport and the nodes.
 
+mod camera;
+mod cull;
+mod knife;
 mod node;
 mod render;
+mod selection;
 
-use egui::{
-    epaint::RectShape, pos2, vec2, CornerRadius, Id, Rect, Shape, StrokeKind, UiBuilder, Vec2,
-};
+use std::collections::{HashMap, HashSet};
+
+use egui::{epaint::RectShape, CornerRadius, Id, Rect, StrokeKind, UiBuilder};
 
-use crate::misc::collector::Collector;
+use crate::{misc::collector::Collector, socket::CanConnect, viewport::CanvasPos, Snapping};
 
-use super::{stages, state::EditorState, GraphEditor, RenderedSocket, Viewport};
+use super::{
+    stages,
+    state::{DragPhase, EditorState, NodeDrag},
+    Arrangement, GraphEditor, KeyBindings, RenderedSocket, Viewport,
+};
 
+pub(super) use cull::NodeGeometry;
+pub(super) use knife::KnifeLine;
 pub use node::{NodeResponse, NodeUi};
 
 /* -------------------------------------------------------------------------- */
@@ -18,28 +28,48 @@ pub use node::{NodeResponse, NodeUi};
 /// This is what you use to render the nodes.
 ///
 /// See [`GraphEditor::show`].
-pub struct GraphUi<S> {
+pub struct GraphUi<'a, S> {
     /// The id of the graph editor.
     pub(super) graph_id: Id,
-    /// The id and delta position of the node being dragged, id any.
-    pub(super) dragged_node: Option<(Id, Vec2)>,
+    /// The nodes being dragged, if any.
+    pub(super) node_drag: Option<NodeDrag>,
     /// The viewport of the editor.
     pub(super) viewport: Viewport,
+    /// How the nodes are snapped when moved.
+    pub(super) snapping: Snapping,
     /// The [`egui::Ui`] used to render the editor.
     pub(super) ui: egui::Ui,
     /// The rendered sockets.
     pub(super) rendered_sockets: Collector<RenderedSocket<S>>,
+    /// The id and UI rect of the rendered nodes.
+    pub(super) node_rects: Vec<(Id, Rect)>,
+    /// The keyboard controls, if enabled.
+    pub(super) key_bindings: Option<KeyBindings>,
+    /// The id of the node that has the focus, if any.
+    pub(super) focused_node: Option<Id>,
+    /// The geometry of the nodes rendered during the last frame.
+    pub(super) last_node_geometries: HashMap<Id, NodeGeometry>,
+    /// The geometry of the nodes rendered during this frame.
+    pub(super) node_geometries: HashMap<Id, NodeGeometry>,
+    /// The ids of the selected nodes.
+    pub(super) selection: HashSet<Id>,
+    /// Whether the user has changed the selection during this frame.
+    pub(super) selection_changed: bool,
+    /// The positions where the arranged nodes are moved.
+    pub(super) placements: HashMap<Id, CanvasPos>,
+    /// The socket from which the user is dragging a connection and which sockets it can connect to.
+    pub(super) connection_filter: Option<(S, &'a CanConnect<'a, S>)>,
 }
 
 /* -------------------------------------------------------------------------- */
 
-impl GraphEditor<stages::Settings> {
+impl<S> GraphEditor<stages::Settings<'_, S>> {
     /// Shows the viewport of the editor.
     #[inline]
-    pub fn show<S>(
+    pub fn show(
         self,
         ui: &mut egui::Ui,
-        build_fn: impl FnOnce(&mut GraphUi<S>),
+        build_fn: impl FnOnce(&mut GraphUi<'_, S>),
     ) -> GraphEditor<stages::Connections<S>>
     where
         S: PartialEq + Send + Sync + Clone + 'static,
@@ -49,9 +79,15 @@ impl GraphEditor<stages::Settings> {
             stage:
                 stages::Settings {
                     show_grid,
-                    look_at,
-                    can_connect_socket,
+                    grid_style,
+                    camera,
+                    connection,
                     viewport,
+                    minimap,
+                    snapping,
+                    key_bindings,
+                    selection,
+                    arrangement,
                 },
         } = self;
 
@@ -83,73 +119,85 @@ impl GraphEditor<stages::Settings> {
 
         let mut state = EditorState::<S>::load(ui.ctx(), id);
 
+        if let Some(selection) = selection {
+            state.selection = selection;
+        }
+
         /* ---- */
 
         let response = ui.interact(rect, id, egui::Sense::click_and_drag());
 
-        if response.dragged() {
-            response.ctx.set_cursor_icon(egui::CursorIcon::Grabbing);
-            state.viewport_position -= response.drag_delta();
-        }
-
-        let viewport = {
-            if let Some(look_at) = look_at {
-                let pos = state.grid.graph_to_canvas(look_at);
-                state.viewport_position = pos;
-            }
+        selection::begin_marquee(&ui, &response, &mut state);
+        knife::begin(&ui, &response, &mut state);
 
-            Viewport {
-                position: rect.center().to_vec2() - state.viewport_position.to_vec2(),
-                grid: state.grid.clone(),
-            }
-        };
+        let viewport = camera::update(&ui, &response, &mut state, &camera, key_bindings.as_ref());
 
         /* ---- */
 
         // Paint the grid
         if show_grid {
-            paint_grid(
-                ui.painter(),
-                rect,
-                state.viewport_position.to_vec2(),
-                state.grid.size,
-                egui::Stroke::new(0.5, ui.visuals().text_color()),
-            );
+            super::grid::paint(&ui, rect, &viewport, &grid_style);
         }
 
         /* ---- */
 
-        let mut graph_ui = GraphUi {
+        // Nodes are rendered with a style scaled by the zoom factor.
+        zoom_style(ui.style_mut(), state.camera.zoom);
+
+        /* ---- */
+
+        let mut graph_ui = GraphUi::new(
             ui,
-            graph_id: id,
-            dragged_node: state.dragged_node,
+            id,
             viewport,
-            rendered_sockets: Collector::new(),
-        };
+            snapping,
+            key_bindings,
+            arrangement,
+            connection.can_connect.as_deref(),
+            &mut state,
+        );
 
         build_fn(&mut graph_ui);
 
-        let GraphUi {
-            graph_id: _,
-            dragged_node,
-            viewport,
-            ui,
-            rendered_sockets,
-        } = graph_ui;
+        let (mut ui, viewport, rendered_sockets, node_rects, selection_changed) =
+            graph_ui.end(&mut state);
 
         /* ---- */
 
-        state.dragged_node = dragged_node;
+        let selection_changed = selection_changed
+            | selection::update(&ui, &response, &viewport, &mut state, &node_rects);
+
+        let knife = knife::update(&ui, &response, &viewport, &mut state);
+
+        /* ---- */
+
+        let nodes_rect = node_rects.iter().map(|(_, rect)| *rect).reduce(Rect::union);
+        state.content_rect = nodes_rect.map(|rect| viewport.viewport_to_canvas_rect(rect));
+
+        /* ---- */
+
+        if let Some(minimap) = minimap {
+            if let Some(pos) = super::minimap::show(
+                &mut ui,
+                id,
+                &minimap,
+                rect,
+                &viewport,
+                &node_rects,
+                &mut state,
+            ) {
+                state.camera.position = pos;
+                state.camera_animation = None;
+                ui.ctx().request_repaint();
+            }
+        }
+
+        /* ---- */
 
         let sockets = rendered_sockets.into_vec();
 
-        let socket_interaction = if can_connect_socket {
-            crate::socket::handle_socket_responses(&mut state.dragged_socket, &sockets)
-        } else {
-            // Stop the currently dragged socket if creating connection is disabled.
-            state.dragged_socket = None;
-            crate::socket::SocketInteraction::None
-        };
+        let socket_interaction =
+            socket_interaction(&mut state, &sockets, &connection, (rect, &node_rects));
 
         /* ---- */
 
@@ -162,6 +210,9 @@ impl GraphEditor<stages::Settings> {
                 response,
                 sockets,
                 socket_interaction,
+                knife,
+                nodes_rect,
+                selection_changed,
             },
         }
     }
@@ -169,45 +220,166 @@ impl GraphEditor<stages::Settings> {
 
 /* -------------------------------------------------------------------------- */
 
-/// Show the editor grid.
-fn paint_grid(
-    painter: &egui::Painter,
-    rect: Rect,
-    position: Vec2,
-    grid_size: f32,
-    stroke: egui::Stroke,
-) {
-    let dx = position.x % grid_size;
-    let dy = position.y % grid_size;
-
-    let center = rect.center() - vec2(dx, dy);
-
-    #[allow(clippy::cast_possible_truncation)]
-    let n = (rect.width() / grid_size) as i32 / 2;
-    #[allow(clippy::cast_possible_truncation)]
-    let m = (rect.height() / grid_size) as i32 / 2;
-
-    for x in (-n)..(n + 2) {
-        #[allow(clippy::cast_precision_loss)]
-        let x = x as f32;
-        let x = x.mul_add(grid_size, center.x);
-
-        painter.add(Shape::LineSegment {
-            points: [pos2(x, rect.min.y), pos2(x, rect.max.y)],
-            stroke,
-        });
+impl<'a, S: Clone> GraphUi<'a, S> {
+    /// Creates the [`GraphUi`], taking the data it needs from the editor state.
+    #[expect(clippy::too_many_arguments, reason = "private function")]
+    fn new(
+        ui: egui::Ui,
+        graph_id: Id,
+        viewport: Viewport,
+        snapping: Snapping,
+        key_bindings: Option<KeyBindings>,
+        arrangement: Option<Arrangement>,
+        can_connect: Option<&'a CanConnect<'a, S>>,
+        state: &mut EditorState<S>,
+    ) -> Self {
+        let placements = arrangement
+            .map(|arrangement| {
+                super::arrange::placements(arrangement, &state.selection, &state.node_geometries)
+            })
+            .unwrap_or_default();
+
+        let node_drag = drop_nodes(&ui, &viewport, state.node_drag.take());
+
+        GraphUi {
+            ui,
+            graph_id,
+            node_drag,
+            viewport,
+            snapping,
+            rendered_sockets: Collector::new(),
+            node_rects: Vec::new(),
+            key_bindings,
+            focused_node: None,
+            last_node_geometries: core::mem::take(&mut state.node_geometries),
+            node_geometries: HashMap::new(),
+            selection: core::mem::take(&mut state.selection),
+            selection_changed: false,
+            placements,
+            connection_filter: state
+                .socket_drag
+                .as_ref()
+                .zip(can_connect)
+                .map(|(drag, can_connect)| (drag.source().clone(), can_connect)),
+        }
+    }
+
+    /// Gives back to the editor state the data updated while rendering the nodes.
+    ///
+    /// Returns the ui, the viewport, the rendered sockets, the id and rect of the rendered nodes
+    /// and whether the selection has changed.
+    #[expect(clippy::type_complexity, reason = "private function")]
+    fn end(
+        self,
+        state: &mut EditorState<S>,
+    ) -> (
+        egui::Ui,
+        Viewport,
+        Collector<RenderedSocket<S>>,
+        Vec<(Id, Rect)>,
+        bool,
+    ) {
+        let GraphUi {
+            graph_id: _,
+            node_drag,
+            viewport,
+            snapping: _,
+            ui,
+            rendered_sockets,
+            node_rects,
+            key_bindings: _,
+            focused_node,
+            last_node_geometries: _,
+            node_geometries,
+            selection,
+            selection_changed,
+            placements: _,
+            connection_filter: _,
+        } = self;
+
+        // The dropped nodes have all been placed during this frame.
+        state.node_drag = node_drag.filter(|drag| drag.phase == DragPhase::Dragging);
+        state.focused_node = focused_node;
+        state.node_geometries = node_geometries;
+        state.selection = selection;
+
+        (
+            ui,
+            viewport,
+            rendered_sockets,
+            node_rects,
+            selection_changed,
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Drops the dragged nodes if the user has released them.
+///
+/// This is done before the nodes are rendered, so they are all placed during the same frame.
+fn drop_nodes(ui: &egui::Ui, viewport: &Viewport, node_drag: Option<NodeDrag>) -> Option<NodeDrag> {
+    let mut drag = node_drag?;
+
+    let (released, pointer_delta) = ui.input(|i| (!i.pointer.any_down(), i.pointer.delta()));
+
+    if drag.phase == DragPhase::Dragging && released {
+        drag.delta += pointer_delta / viewport.zoom;
+        drag.phase = DragPhase::Dropped;
     }
 
-    for y in (-m)..(m + 2) {
-        #[allow(clippy::cast_precision_loss)]
-        let y = y as f32;
-        let y = y.mul_add(grid_size, center.y);
+    Some(drag)
+}
 
-        painter.add(Shape::LineSegment {
-            points: [pos2(rect.min.x, y), pos2(rect.max.x, y)],
-            stroke,
-        });
+/// Handles the drag-n-drop of the sockets.
+fn socket_interaction<S>(
+    state: &mut EditorState<S>,
+    sockets: &[RenderedSocket<S>],
+    settings: &stages::ConnectionSettings<'_, S>,
+    (rect, node_rects): (Rect, &[(Id, Rect)]),
+) -> crate::socket::SocketInteraction<S>
+where
+    S: Clone + PartialEq,
+{
+    if !settings.enabled {
+        // Stop the currently dragged socket if creating connection is disabled.
+        state.socket_drag = None;
+        return crate::socket::SocketInteraction::None;
     }
+
+    let interaction = crate::socket::handle_socket_responses(
+        &mut state.socket_drag,
+        sockets,
+        settings.rewire.then_some(state.connections.as_slice()),
+        |a, b| {
+            settings
+                .can_connect
+                .as_ref()
+                .map_or(true, |can_connect| can_connect(a, b))
+        },
+        settings.snap_radius,
+    );
+
+    if let crate::socket::SocketInteraction::Dropped(_, pos) = interaction {
+        // Only the connections dropped on the empty canvas are reported.
+        if !rect.contains(pos) || node_rects.iter().any(|(_, r)| r.contains(pos)) {
+            return crate::socket::SocketInteraction::None;
+        }
+    }
+
+    interaction
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Scales the sizes of the style by the zoom factor.
+fn zoom_style(style: &mut egui::Style, zoom: f32) {
+    for font_id in style.text_styles.values_mut() {
+        font_id.size *= zoom;
+    }
+
+    style.spacing.item_spacing *= zoom;
+    style.visuals.window_corner_radius *= zoom;
 }
 
 /* -------------------------------------------------------------------------- */
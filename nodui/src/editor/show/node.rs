@@ -1,17 +1,21 @@
 //! Node rendering.
 
+use std::collections::HashSet;
+
 use egui::{
     epaint::RectShape, vec2, Color32, CornerRadius, Pos2, Rect, Response, StrokeKind, UiBuilder,
     Vec2,
 };
 
 use crate::{
+    editor::state::{DragPhase, NodeDrag},
     misc::{collector::Collector, layout},
-    Header, NodeLayout, Pos, RenderedSocket, Socket,
+    viewport::{CanvasPos, Grid, Viewport},
+    FreePos, Header, NodeLayout, NodePos, RenderedSocket, Snapping, Socket,
 };
 
 use super::render::{self, body::PreparedBody, header::PreparedHeader};
-use super::GraphUi;
+use super::{cull::NodeGeometry, GraphUi};
 
 /* -------------------------------------------------------------------------- */
 
@@ -41,46 +45,64 @@ pub struct NodeResponse<'a, R, S> {
     pub response: Response,
     /// The rendered socket of the node.
     pub sockets: &'a [RenderedSocket<S>],
+    /// Whether the node is selected.
+    pub selected: bool,
 }
 
 /* -------------------------------------------------------------------------- */
 
-impl<S> GraphUi<S> {
+impl<S> GraphUi<'_, S> {
     /// Render a node.
     ///
     /// `id_salt` must be a unique id for the node.
+    ///
+    /// `pos` is either a [`Pos`](crate::Pos), to place the node on the grid, or a [`FreePos`]
+    /// for a free placement.
     #[inline]
-    pub fn node<'a, R>(
+    pub fn node<'a, R, P>(
         &mut self,
         id_salt: impl core::hash::Hash,
-        pos: &mut Pos,
+        pos: &mut P,
         build_fn: impl FnOnce(&mut NodeUi<S>) -> R + 'a,
     ) -> NodeResponse<'_, R, S>
     where
-        S: core::hash::Hash,
+        S: PartialEq + core::hash::Hash,
+        P: NodePos,
     {
         let mut node_ui = NodeUi::new();
         let inner = build_fn(&mut node_ui);
-        let node = node_ui.prepare(&self.ui);
+
+        self.dim_unconnectable_sockets(&mut node_ui.sockets);
 
         let id = self.graph_id.with(id_salt);
 
-        let canvas_pos = {
-            let delta_pos = match self.dragged_node {
-                Some((dragged_id, delta_pos)) if dragged_id == id => delta_pos,
-                _ => Vec2::ZERO,
-            };
+        self.handle_focus(id, pos);
 
-            self.viewport.grid.graph_to_canvas(*pos) + delta_pos
-        };
+        let (canvas_pos, is_dragged) = self.node_canvas_pos(id, pos);
 
         let ui_pos = self.viewport.canvas_to_viewport(canvas_pos);
+        let zoom = self.viewport.zoom;
+
+        // Skip the layout and the painting of the nodes outside of the visible area.
+        let culled_geometry = self.last_node_geometries.remove(&id).filter(|geometry| {
+            !is_dragged && geometry.is_culled(self.ui.clip_rect(), ui_pos, zoom, &node_ui.sockets)
+        });
+
+        let content = match culled_geometry {
+            Some(geometry) => NodeContent::Culled(geometry, node_ui.sockets),
+            None => {
+                NodeContent::Visible(node_ui.prepare(&self.ui, zoom, self.selection.contains(&id)))
+            }
+        };
 
-        let node_size = node.size();
+        let node_size = match &content {
+            NodeContent::Visible(node) => node.size(),
+            NodeContent::Culled(geometry, _) => geometry.size(zoom),
+        };
 
         let layer_id = egui::LayerId::new(egui::Order::Middle, id);
 
-        let (response, sockets) = self
+        let (response, sockets, culled_geometry) = self
             .ui
             .scope_builder(UiBuilder::new().layer_id(layer_id), |ui| {
                 let response = ui.interact(
@@ -89,26 +111,45 @@ impl<S> GraphUi<S> {
                     egui::Sense::click_and_drag(),
                 );
 
-                let (sockets, ()) = self.rendered_sockets.watch(|rendered_sockets| {
-                    node.show(ui, ui_pos, rendered_sockets);
-                });
-
-                (response, sockets)
+                let (sockets, culled_geometry) =
+                    self.rendered_sockets
+                        .watch(|rendered_sockets| match content {
+                            NodeContent::Visible(node) => {
+                                node.show(ui, ui_pos, rendered_sockets);
+                                None
+                            }
+                            NodeContent::Culled(geometry, sockets) => {
+                                geometry.show_culled(ui, ui_pos, zoom, sockets, rendered_sockets);
+                                Some(geometry)
+                            }
+                        });
+
+                (response, sockets, culled_geometry)
             })
             .inner;
 
-        if response.drag_stopped() {
-            self.dragged_node = None;
-            let new_pos = canvas_pos + response.drag_delta();
-            // node.set_pos(viewport.grid.canvas_to_graph_nearest(new_pos));
-            *pos = self.viewport.grid.canvas_to_graph_nearest(new_pos);
-        } else if response.drag_started() {
-            self.dragged_node = Some((id, response.drag_delta()));
-        } else if response.dragged() {
-            if let Some(dragged_node) = self.dragged_node.as_mut() {
-                dragged_node.1 += response.drag_delta();
-            }
+        let geometry = match culled_geometry {
+            Some(geometry) => geometry.moved_to(canvas_pos),
+            None => NodeGeometry::new(canvas_pos, ui_pos, node_size, sockets, zoom),
+        };
+        self.node_geometries.insert(id, geometry);
+
+        self.node_rects.push((id, response.rect));
+
+        let modifiers = self.ui.input(|i| i.modifiers);
+        if super::selection::select_node(&mut self.selection, id, &response, modifiers) {
+            self.selection_changed = true;
+            self.ui.ctx().request_repaint();
         }
+        let selected = self.selection.contains(&id);
+
+        update_drag(
+            &mut self.node_drag,
+            &self.selection,
+            id,
+            &response,
+            &self.viewport,
+        );
 
         if response.flags.contains(egui::response::Flags::CLICKED)
             || response
@@ -124,10 +165,141 @@ impl<S> GraphUi<S> {
             inner,
             response,
             sockets,
+            selected,
         }
     }
 }
 
+impl<S> GraphUi<'_, S> {
+    /// The canvas position where the node is rendered and whether it is the node dragged by the user.
+    ///
+    /// Places the node if it has been dropped or arranged.
+    fn node_canvas_pos<P: NodePos>(&mut self, id: egui::Id, pos: &mut P) -> (CanvasPos, bool) {
+        let grid = &self.viewport.grid;
+
+        if let Some(placement) = self.placements.remove(&id) {
+            // The node is moved by an arrangement.
+            *pos = snap(grid, self.snapping, placement);
+        }
+
+        let canvas_pos = grid.free_to_canvas(pos.to_free_pos());
+
+        let Some(drag) = self.node_drag.as_mut() else {
+            return (canvas_pos, false);
+        };
+
+        match drag.phase {
+            DragPhase::Dragging if drag.nodes.contains(&id) => {
+                // Show the node where it will be placed when dropped.
+                let snapped_pos: P = snap(grid, self.snapping, canvas_pos + drag.delta);
+                (
+                    grid.free_to_canvas(snapped_pos.to_free_pos()),
+                    drag.leader == id,
+                )
+            }
+            DragPhase::Dropped if drag.nodes.remove(&id) => {
+                *pos = snap(grid, self.snapping, canvas_pos + drag.delta);
+                (grid.free_to_canvas(pos.to_free_pos()), false)
+            }
+            DragPhase::Dragging | DragPhase::Dropped => (canvas_pos, false),
+        }
+    }
+
+    /// Greys out the sockets the dragged connection cannot be connected to.
+    fn dim_unconnectable_sockets(&self, sockets: &mut [Socket<S>])
+    where
+        S: PartialEq,
+    {
+        let Some((source, can_connect)) = &self.connection_filter else {
+            return;
+        };
+
+        for socket in sockets {
+            if socket.id != *source && !can_connect(source, &socket.id) {
+                if socket.color == Color32::PLACEHOLDER {
+                    socket.color = self.ui.visuals().strong_text_color();
+                }
+                socket.color = socket.color.gamma_multiply(DIMMED_SOCKET_OPACITY);
+            }
+        }
+    }
+
+    /// Keeps track of the focused node and moves it with the arrow keys.
+    fn handle_focus<P: NodePos>(&mut self, id: egui::Id, pos: &mut P) {
+        if !self.ui.memory(|mem| mem.has_focus(id)) {
+            return;
+        }
+
+        self.focused_node = Some(id);
+
+        if let Some(key_bindings) = &self.key_bindings {
+            // Keep the focus on the node when the arrows are pressed.
+            super::camera::lock_arrows_focus(&self.ui, id);
+
+            let direction = key_bindings.consume_direction(&self.ui);
+            if direction != Vec2::ZERO {
+                let FreePos { x, y } = pos.to_free_pos();
+                *pos = P::from_free_pos(FreePos::new(x + direction.x, y + direction.y));
+            }
+        }
+    }
+}
+
+/// The opacity of the sockets the dragged connection cannot be connected to.
+const DIMMED_SOCKET_OPACITY: f32 = 0.25;
+
+/// What is rendered for a node.
+enum NodeContent<S> {
+    /// The node is visible and fully rendered.
+    Visible(PreparedNode<S>),
+    /// The node is outside of the visible area, only its sockets are registered.
+    Culled(NodeGeometry, Vec<Socket<S>>),
+}
+
+/// Updates the drag of the nodes with the response of the node `id`.
+///
+/// When the drag starts on a selected node, all the selected nodes are moved.
+///
+/// The nodes are dropped before being rendered, see `drop_nodes`.
+fn update_drag(
+    node_drag: &mut Option<NodeDrag>,
+    selection: &HashSet<egui::Id>,
+    id: egui::Id,
+    response: &Response,
+    viewport: &Viewport,
+) {
+    let delta = response.drag_delta() / viewport.zoom;
+
+    if response.drag_started() {
+        let nodes = if selection.contains(&id) {
+            selection.clone()
+        } else {
+            HashSet::from([id])
+        };
+
+        *node_drag = Some(NodeDrag {
+            leader: id,
+            nodes,
+            delta,
+            phase: DragPhase::Dragging,
+        });
+
+        return;
+    }
+
+    if let Some(drag) = node_drag
+        .as_mut()
+        .filter(|drag| drag.leader == id && drag.phase == DragPhase::Dragging)
+    {
+        drag.delta += delta;
+    }
+}
+
+/// Converts a canvas position into a node position, applying the snapping.
+fn snap<P: NodePos>(grid: &Grid, snapping: Snapping, pos: CanvasPos) -> P {
+    P::from_free_pos(snapping.snap(grid.canvas_to_free(pos)))
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl<S> NodeUi<S> {
@@ -143,7 +315,9 @@ impl<S> NodeUi<S> {
     }
 
     /// Do the computations required to render the node.
-    fn prepare(self, ui: &egui::Ui) -> PreparedNode<S> {
+    ///
+    /// The `ui` style is expected to be already scaled by `zoom`.
+    fn prepare(self, ui: &egui::Ui, zoom: f32, selected: bool) -> PreparedNode<S> {
         let Self {
             header,
             mut background_color,
@@ -156,15 +330,20 @@ impl<S> NodeUi<S> {
             background_color = ui.visuals().extreme_bg_color;
         }
 
-        let outline = outline.unwrap_or(ui.visuals().window_stroke);
+        let mut outline = if selected {
+            ui.visuals().selection.stroke
+        } else {
+            outline.unwrap_or(ui.visuals().window_stroke)
+        };
+        outline.width *= zoom;
 
-        let header = render::header::prepare(ui, header, background_color);
+        let header = render::header::prepare(ui, header, background_color, zoom);
 
         let sockets = sockets
             .into_iter()
-            .map(|s| render::socket::prepare(ui, s))
+            .map(|s| render::socket::prepare(ui, s, zoom))
             .collect();
-        let body = render::body::prepare(ui.spacing(), background_color, layout, sockets);
+        let body = render::body::prepare(ui.spacing(), background_color, layout, sockets, zoom);
 
         PreparedNode {
             header,
@@ -24,6 +24,8 @@ pub(crate) struct PreparedBody<S> {
 
     /// The padding around the body.
     padding: Margin,
+    /// The zoom factor used to render the body.
+    zoom: f32,
 }
 
 impl<S> PreparedBody<S> {
@@ -41,8 +43,9 @@ pub(crate) fn prepare<S>(
     background_color: Color32,
     layout: NodeLayout,
     sockets: Vec<PreparedSocket<S>>,
+    zoom: f32,
 ) -> PreparedBody<S> {
-    let padding = Margin::same(5);
+    let padding = Margin::same(5) * zoom;
     let socket_vertical_gap = spacing.item_spacing.y;
 
     let size: Vec2 = match layout {
@@ -87,6 +90,7 @@ pub(crate) fn prepare<S>(
         background_color,
 
         padding,
+        zoom,
     }
 }
 
@@ -111,6 +115,7 @@ where
             layout,
             background_color,
             padding,
+            zoom,
         } = self;
 
         let rect = Rect::from_min_size(pos, vec2(node_size.x, size.y));
@@ -123,10 +128,10 @@ where
 
             match layout {
                 NodeLayout::Single => {
-                    show_single_column_body(ui, rendered_sockets, sockets, rect);
+                    show_single_column_body(ui, rendered_sockets, sockets, rect, zoom);
                 }
                 NodeLayout::Double => {
-                    show_double_column_body(ui, rendered_sockets, sockets, rect);
+                    show_double_column_body(ui, rendered_sockets, sockets, rect, zoom);
                 }
             }
         }
@@ -152,15 +157,18 @@ struct SocketGeometries {
 }
 
 /// Computes the socket geometry based on the width available.
-fn compute_socket_geometries(width: f32) -> SocketGeometries {
+fn compute_socket_geometries(width: f32, zoom: f32) -> SocketGeometries {
+    let socket_width = SOCKET_WIDTH * zoom;
+    let name_gap = SOCKET_NAME_GAP * zoom;
+
     SocketGeometries {
         left: SocketGeometry {
             socket_x: 0.0,
-            text_x: SOCKET_WIDTH + SOCKET_NAME_GAP,
+            text_x: socket_width + name_gap,
         },
         right: SocketGeometry {
-            socket_x: width - SOCKET_WIDTH,
-            text_x: width - (SOCKET_WIDTH + SOCKET_NAME_GAP),
+            socket_x: width - socket_width,
+            text_x: width - (socket_width + name_gap),
         },
     }
 }
@@ -171,10 +179,11 @@ fn show_single_column_body<S>(
     rendered_sockets: &mut Collector<RenderedSocket<S>>,
     sockets: Vec<PreparedSocket<S>>,
     rect: Rect,
+    zoom: f32,
 ) where
     S: core::hash::Hash,
 {
-    let geometry = compute_socket_geometries(rect.width());
+    let geometry = compute_socket_geometries(rect.width(), zoom);
     let mut pos = rect.min;
 
     for socket in sockets {
@@ -193,10 +202,11 @@ fn show_double_column_body<S>(
     rendered_sockets: &mut Collector<RenderedSocket<S>>,
     sockets: Vec<PreparedSocket<S>>,
     rect: Rect,
+    zoom: f32,
 ) where
     S: core::hash::Hash,
 {
-    let geometry = compute_socket_geometries(rect.width());
+    let geometry = compute_socket_geometries(rect.width(), zoom);
     let mut left = rect.min;
     let mut right = rect.min;
 
@@ -221,7 +231,8 @@ fn show_socket<S>(
     S: core::hash::Hash,
 {
     let size = socket.compute_size();
-    let socket_center = *pos + vec2(geometry.socket_x + SOCKET_WIDTH / 2.0, size.y / 2.0);
+    let socket_width = socket.handle_width();
+    let socket_center = *pos + vec2(geometry.socket_x + socket_width / 2.0, size.y / 2.0);
     let text_pos = *pos + vec2(geometry.text_x, (size.y - socket.text.rect.height()) / 2.0);
 
     pos.y += size.y + ui.spacing().item_spacing.y;
@@ -233,10 +244,11 @@ fn show_socket<S>(
         filled: is_connected,
         color,
         shape,
+        zoom: _,
     } = socket;
 
     {
-        let rect = Rect::from_center_size(socket_center, Vec2::splat(SOCKET_WIDTH));
+        let rect = Rect::from_center_size(socket_center, Vec2::splat(socket_width));
         let response = ui.interact(rect, ui.id().with(&id), egui::Sense::click_and_drag());
         let response = response.on_hover_cursor(egui::CursorIcon::PointingHand);
         rendered_sockets.push(RenderedSocket {
@@ -248,7 +260,7 @@ fn show_socket<S>(
     }
 
     ui.painter()
-        .add(shape.to_shape(socket_center, SOCKET_WIDTH, color, is_connected));
+        .add(shape.to_shape(socket_center, socket_width, color, is_connected));
 
     ui.painter().add(egui::Shape::galley(
         text_pos,
@@ -52,7 +52,12 @@ struct TitleHeaderContent {
 /* -------------------------------------------------------------------------- */
 
 /// Do computations to render the header.
-pub(crate) fn prepare(ui: &egui::Ui, header: Header, body_color: Color32) -> PreparedHeader {
+pub(crate) fn prepare(
+    ui: &egui::Ui,
+    header: Header,
+    body_color: Color32,
+    zoom: f32,
+) -> PreparedHeader {
     match header {
         Header::None => PreparedHeader {
             content: HeaderContent::None,
@@ -63,7 +68,7 @@ pub(crate) fn prepare(ui: &egui::Ui, header: Header, body_color: Color32) -> Pre
             background_color: background,
         }) => {
             // TODO: allow user to customize this value ?
-            let padding = egui::Margin::same(5);
+            let padding = egui::Margin::same(5) * zoom;
 
             let background = if background == Color32::PLACEHOLDER {
                 body_color
@@ -24,13 +24,25 @@ pub(crate) struct PreparedSocket<SocketId> {
     pub(super) color: Color32,
     /// The shape of the socket's handle.
     pub(super) shape: SocketShape,
+    /// The zoom factor used to render the socket.
+    pub(super) zoom: f32,
 }
 
 impl<S> PreparedSocket<S> {
+    /// The width of the socket's handle.
+    pub(super) fn handle_width(&self) -> f32 {
+        SOCKET_WIDTH * self.zoom
+    }
+
+    /// The space between the socket's name and its handle.
+    pub(super) fn name_gap(&self) -> f32 {
+        SOCKET_NAME_GAP * self.zoom
+    }
+
     /// Compute the size the socket will occupied.
     pub(super) fn compute_size(&self) -> Vec2 {
-        let socket_size = Vec2::splat(SOCKET_WIDTH);
-        let socket_text_gap = vec2(SOCKET_NAME_GAP, 0.0);
+        let socket_size = Vec2::splat(self.handle_width());
+        let socket_text_gap = vec2(self.name_gap(), 0.0);
         let text_size = self.text.size();
 
         layout::stack_horizontally([socket_size, socket_text_gap, text_size])
@@ -40,7 +52,7 @@ impl<S> PreparedSocket<S> {
 /* -------------------------------------------------------------------------- */
 
 /// Do computations to render a socket.
-pub(crate) fn prepare<S>(ui: &egui::Ui, socket: Socket<S>) -> PreparedSocket<S> {
+pub(crate) fn prepare<S>(ui: &egui::Ui, socket: Socket<S>, zoom: f32) -> PreparedSocket<S> {
     let Socket {
         id,
         side,
@@ -78,6 +90,7 @@ pub(crate) fn prepare<S>(ui: &egui::Ui, socket: Socket<S>) -> PreparedSocket<S>
         filled,
         color,
         shape,
+        zoom,
     }
 }
 
@@ -0,0 +1,277 @@
+//! Selection of the nodes.
+
+use std::collections::HashSet;
+
+use egui::{
+    epaint::{PathShape, RectShape},
+    CornerRadius, Id, Pos2, Rect, StrokeKind,
+};
+
+use crate::editor::state::{EditorState, Marquee};
+
+use super::Viewport;
+
+/* -------------------------------------------------------------------------- */
+
+/// Whether the modifiers add nodes to the selection instead of replacing it.
+fn is_additive(modifiers: egui::Modifiers) -> bool {
+    modifiers.shift || modifiers.command
+}
+
+/// Updates the selection when the user clicks or starts dragging a node.
+///
+/// Returns `true` if the selection has changed.
+pub(super) fn select_node(
+    selection: &mut HashSet<Id>,
+    id: Id,
+    response: &egui::Response,
+    modifiers: egui::Modifiers,
+) -> bool {
+    if response.clicked() {
+        if is_additive(modifiers) {
+            // Toggle the node.
+            if !selection.remove(&id) {
+                selection.insert(id);
+            }
+            true
+        } else {
+            select_only(selection, id)
+        }
+    } else if response.drag_started() && !selection.contains(&id) {
+        if is_additive(modifiers) {
+            selection.insert(id)
+        } else {
+            select_only(selection, id)
+        }
+    } else {
+        false
+    }
+}
+
+/// Selects only the node `id`.
+///
+/// Returns `true` if the selection has changed.
+fn select_only(selection: &mut HashSet<Id>, id: Id) -> bool {
+    if selection.len() == 1 && selection.contains(&id) {
+        false
+    } else {
+        selection.clear();
+        selection.insert(id);
+        true
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Starts a marquee selection when the user drags the empty canvas while holding
+/// <kbd>Shift</kbd> or <kbd>Ctrl</kbd>, or a lasso selection while holding <kbd>Alt</kbd>.
+///
+/// A plain drag moves the viewport. The selected nodes are added to the selection while holding
+/// <kbd>Shift</kbd> or <kbd>Ctrl</kbd>, otherwise they replace it.
+///
+/// Must be called before the camera is updated so the drag doesn't move the viewport.
+pub(super) fn begin_marquee<S>(
+    ui: &egui::Ui,
+    response: &egui::Response,
+    state: &mut EditorState<S>,
+) {
+    if !response.drag_started_by(egui::PointerButton::Primary) {
+        return;
+    }
+
+    let (modifiers, press_origin) = ui.input(|i| (i.modifiers, i.pointer.press_origin()));
+
+    if !is_additive(modifiers) && !modifiers.alt {
+        return;
+    }
+
+    if let Some(press_origin) = press_origin {
+        let offset = press_origin - response.rect.center();
+        let start = state.camera.position + offset / state.camera.zoom;
+
+        state.marquee = Some(Marquee {
+            start,
+            additive: is_additive(modifiers),
+            lasso: modifiers.alt.then(|| vec![start]),
+        });
+    }
+}
+
+/// The minimum distance between two points of a lasso, in pixels.
+const LASSO_MIN_DISTANCE: f32 = 4.0;
+
+/// Updates the on-going marquee selection and clears the selection when the user clicks the empty canvas.
+///
+/// Returns `true` if the selection has changed.
+pub(super) fn update<S>(
+    ui: &egui::Ui,
+    response: &egui::Response,
+    viewport: &Viewport,
+    state: &mut EditorState<S>,
+    node_rects: &[(Id, Rect)],
+) -> bool {
+    let selection = &mut state.selection;
+
+    let Some(marquee) = state.marquee.as_mut() else {
+        // Clicking a connection doesn't clear the selection.
+        if response.clicked()
+            && state.hovered_connection.is_none()
+            && !is_additive(ui.input(|i| i.modifiers))
+            && !selection.is_empty()
+        {
+            selection.clear();
+            return true;
+        }
+        return false;
+    };
+
+    let Some(pointer_pos) = ui.input(|i| i.pointer.latest_pos()) else {
+        return false;
+    };
+
+    let area = if let Some(lasso) = marquee.lasso.as_mut() {
+        let pointer_pos = viewport.viewport_to_canvas(pointer_pos);
+        let is_too_close = lasso.last().is_some_and(|last| {
+            (pointer_pos.to_vec2() - last.to_vec2()).length() * viewport.zoom < LASSO_MIN_DISTANCE
+        });
+        if !is_too_close {
+            lasso.push(pointer_pos);
+        }
+
+        Area::Lasso(
+            lasso
+                .iter()
+                .map(|pos| viewport.canvas_to_viewport(*pos))
+                .collect(),
+        )
+    } else {
+        Area::Rect(Rect::from_two_pos(
+            viewport.canvas_to_viewport(marquee.start),
+            pointer_pos,
+        ))
+    };
+
+    if !response.dragged() {
+        // The marquee has been released.
+        let additive = marquee.additive;
+        state.marquee = None;
+
+        let in_area = node_rects
+            .iter()
+            .filter(|(_, rect)| area.intersects(*rect))
+            .map(|(id, _)| *id);
+
+        if additive {
+            let previous_len = selection.len();
+            selection.extend(in_area);
+            return selection.len() != previous_len;
+        }
+
+        let new_selection: HashSet<Id> = in_area.collect();
+        let changed = new_selection != *selection;
+        *selection = new_selection;
+        return changed;
+    }
+
+    let layer_id = egui::LayerId::new(egui::Order::Foreground, response.id.with("marquee"));
+    area.paint(&ui.painter().clone().with_layer_id(layer_id), ui.visuals());
+
+    false
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The area of a marquee selection, in UI coordinates.
+enum Area {
+    /// A rectangle selection.
+    Rect(Rect),
+    /// A free-form selection.
+    Lasso(Vec<Pos2>),
+}
+
+impl Area {
+    /// Whether the area intersects `rect`.
+    fn intersects(&self, rect: Rect) -> bool {
+        match self {
+            Area::Rect(area) => area.intersects(rect),
+            Area::Lasso(polygon) => polygon_intersects_rect(polygon, rect),
+        }
+    }
+
+    /// Paints the area.
+    fn paint(&self, painter: &egui::Painter, visuals: &egui::Visuals) {
+        let stroke = visuals.selection.stroke;
+
+        match self {
+            Area::Rect(rect) => {
+                painter.add(RectShape::new(
+                    *rect,
+                    CornerRadius::ZERO,
+                    visuals.selection.bg_fill.gamma_multiply(0.25),
+                    stroke,
+                    StrokeKind::Inside,
+                ));
+            }
+            Area::Lasso(points) => {
+                painter.add(PathShape::closed_line(points.clone(), stroke));
+            }
+        }
+    }
+}
+
+/// Whether the polygon, implicitly closed, intersects `rect`.
+fn polygon_intersects_rect(polygon: &[Pos2], rect: Rect) -> bool {
+    if polygon.iter().any(|point| rect.contains(*point)) {
+        return true;
+    }
+
+    // The rect may be entirely inside the polygon.
+    if contains_point(polygon, rect.center()) {
+        return true;
+    }
+
+    let corners = [
+        rect.left_top(),
+        rect.right_top(),
+        rect.right_bottom(),
+        rect.left_bottom(),
+    ];
+
+    edges(polygon).any(|(a, b)| edges(&corners).any(|(c, d)| segments_intersect(a, b, c, d)))
+}
+
+/// The edges of the polygon, including the closing one.
+fn edges(polygon: &[Pos2]) -> impl Iterator<Item = (Pos2, Pos2)> + '_ {
+    polygon
+        .iter()
+        .copied()
+        .zip(polygon.iter().copied().cycle().skip(1))
+}
+
+/// Whether the polygon, implicitly closed, contains `point`.
+///
+/// Uses the even-odd rule.
+fn contains_point(polygon: &[Pos2], point: Pos2) -> bool {
+    edges(polygon)
+        .filter(|(a, b)| {
+            (a.y > point.y) != (b.y > point.y)
+                && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+        })
+        .count()
+        % 2
+        == 1
+}
+
+/// Whether the segments `[a, b]` and `[c, d]` intersect.
+pub(super) fn segments_intersect(a: Pos2, b: Pos2, c: Pos2, d: Pos2) -> bool {
+    let cross = |o: Pos2, p: Pos2, q: Pos2| (p - o).x * (q - o).y - (p - o).y * (q - o).x;
+
+    let d1 = cross(c, d, a);
+    let d2 = cross(c, d, b);
+    let d3 = cross(a, b, c);
+    let d4 = cross(a, b, d);
+
+    (d1 > 0.0) != (d2 > 0.0) && (d3 > 0.0) != (d4 > 0.0)
+}
+
+/* -------------------------------------------------------------------------- */
@@ -1,17 +1,70 @@
 //! Stages of [`GraphEditor`](super::GraphEditor).
 
-use crate::{misc::viewport::ViewportSize, Pos};
+use crate::{misc::viewport::ViewportSize, GraphRect, Pos};
 
 /// Render the viewport of the graph editor.
-pub struct Settings {
+///
+/// `S` is the type of the socket ids and `'a` the lifetime of the [`can_connect`](super::GraphEditor::can_connect) callback.
+pub struct Settings<'a, S> {
     /// Should show the grid.
     pub(super) show_grid: bool,
-    /// Sets the position of the viewport.
-    pub(super) look_at: Option<Pos>,
-    /// Can the user drag a socket to start a new connection.
-    pub(super) can_connect_socket: bool,
+    /// The style of the grid.
+    pub(super) grid_style: super::GridStyle,
+    /// The settings of the camera.
+    pub(super) camera: CameraSettings,
+    /// The settings of the connections created by the user.
+    pub(super) connection: ConnectionSettings<'a, S>,
     /// The size of the viewport
     pub(super) viewport: ViewportSize,
+    /// The minimap to show, if any.
+    pub(super) minimap: Option<super::Minimap>,
+    /// How the nodes are snapped when moved.
+    pub(super) snapping: crate::Snapping,
+    /// The keyboard controls, if enabled.
+    pub(super) key_bindings: Option<super::KeyBindings>,
+    /// The selection to restore, if any.
+    pub(super) selection: Option<std::collections::HashSet<egui::Id>>,
+    /// The arrangement to apply to the selected nodes, if any.
+    pub(super) arrangement: Option<super::Arrangement>,
+}
+
+/// The settings of the camera of the editor.
+pub(super) struct CameraSettings {
+    /// The viewport state to restore, if any.
+    pub(super) restore: Option<super::ViewportState>,
+    /// Where the viewport should move to.
+    pub(super) target: Option<CameraTarget>,
+    /// The duration of the camera movements toward a target, in seconds.
+    pub(super) animation_time: Option<f32>,
+    /// Whether the viewport moves when something is dragged near its border.
+    pub(super) auto_pan: bool,
+    /// The minimum zoom factor.
+    pub(super) min_zoom: f32,
+    /// The maximum zoom factor.
+    pub(super) max_zoom: f32,
+}
+
+/// The settings of the connections created by the user.
+pub(super) struct ConnectionSettings<'a, S> {
+    /// Can the user drag a socket to start a new connection.
+    pub(super) enabled: bool,
+    /// Which connections the user can create.
+    pub(super) can_connect: Option<Box<crate::socket::CanConnect<'a, S>>>,
+    /// The distance from the pointer within which a dragged connection snaps to a socket.
+    pub(super) snap_radius: f32,
+    /// Can the user pick up a connection from its input socket.
+    pub(super) rewire: bool,
+}
+
+/// Where the viewport should move to.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum CameraTarget {
+    /// Center the viewport on a position.
+    LookAt(Pos),
+    /// Fit all the nodes in the viewport.
+    FitAll,
+    /// Fit a rect in the viewport.
+    FitRect(GraphRect),
 }
 
 /// Render the connections.
@@ -19,7 +72,7 @@ pub struct Connections<S> {
     /// The [`egui::Ui`] used to render the editor.
     pub(super) ui: egui::Ui,
     /// The state of the editor.
-    pub(super) state: super::EditorState<S>,
+    pub(super) state: super::state::LoadedState<S>,
     /// The viewport used for coordinates conversions.
     pub(super) viewport: super::Viewport,
     /// The response of the editor.
@@ -28,4 +81,10 @@ pub struct Connections<S> {
     pub(super) sockets: Vec<super::RenderedSocket<S>>,
     /// A user interaction with the sockets.
     pub(super) socket_interaction: crate::socket::SocketInteraction<S>,
+    /// The line of the knife released during this frame, if any.
+    pub(super) knife: Option<super::show::KnifeLine>,
+    /// The UI rect that contains all the rendered nodes, if any.
+    pub(super) nodes_rect: Option<egui::Rect>,
+    /// Whether the user has changed the selection during this frame.
+    pub(super) selection_changed: bool,
 }
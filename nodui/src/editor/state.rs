@@ -1,28 +1,117 @@
 //! State of the editor saved between frames.
 
-use super::{CanvasPos, Grid};
+use std::collections::{HashMap, HashSet};
+
+use crate::socket::SocketDrag;
+
+use super::{
+    connections::RouteCache, show::NodeGeometry, CanvasPos, CanvasRect, Grid, ViewportState,
+};
 
 /// The state of the editor saved from on frame to another.
 #[derive(Clone)]
 pub(super) struct EditorState<S> {
-    /// The current viewport position.
-    pub(super) viewport_position: CanvasPos,
+    /// The current position and zoom factor of the viewport.
+    pub(super) camera: ViewportState,
     /// The grid of the editor.
     pub(super) grid: Grid,
+    /// The running animation of the camera, if any.
+    pub(super) camera_animation: Option<CameraAnimation>,
+    /// The area occupied by the nodes rendered during the last frame.
+    pub(super) content_rect: Option<CanvasRect>,
+    /// The area shown by the minimap, kept while the user drags the minimap.
+    pub(super) minimap_world: Option<CanvasRect>,
 
-    /// The node currently being dragged and the delta position form it's current position.
-    pub(super) dragged_node: Option<(egui::Id, egui::Vec2)>,
+    /// The nodes currently being dragged, if any.
+    pub(super) node_drag: Option<NodeDrag>,
     /// The socket currently being dragged.
-    pub(super) dragged_socket: Option<S>,
+    pub(super) socket_drag: Option<SocketDrag<S>>,
+    /// The connections rendered during the last frame.
+    pub(super) connections: Vec<(S, S)>,
+    /// The node that had the focus during the last frame.
+    pub(super) focused_node: Option<egui::Id>,
+    /// The geometry of the nodes rendered during the last frame, used to cull them.
+    pub(super) node_geometries: HashMap<egui::Id, NodeGeometry>,
+
+    /// The ids of the selected nodes.
+    pub(super) selection: HashSet<egui::Id>,
+    /// The on-going marquee selection, if any.
+    pub(super) marquee: Option<Marquee>,
+
+    /// The points of the on-going knife cut, if any.
+    pub(super) knife: Option<Vec<CanvasPos>>,
+    /// The connection hovered during the last frame, if any.
+    pub(super) hovered_connection: Option<egui::Id>,
+    /// The connections routed around the nodes during the previous frames.
+    pub(super) routes: RouteCache,
+}
+
+/// An animation of the camera toward a target.
+#[derive(Clone, Copy)]
+pub(super) struct CameraAnimation {
+    /// The state of the camera when the animation started.
+    pub(super) from: ViewportState,
+    /// The state of the camera at the end of the animation.
+    pub(super) to: ViewportState,
+    /// When the animation started, see [`egui::InputState::time`].
+    pub(super) start_time: f64,
+    /// The duration of the animation, in seconds.
+    pub(super) duration: f32,
+}
+
+/// A rectangle or free-form selection of nodes.
+#[derive(Clone)]
+pub(super) struct Marquee {
+    /// The position where the selection started.
+    pub(super) start: CanvasPos,
+    /// Whether the nodes are added to the current selection instead of replacing it.
+    pub(super) additive: bool,
+    /// The points of the lasso, if this is a free-form selection.
+    pub(super) lasso: Option<Vec<CanvasPos>>,
+}
+
+/// A drag of one or several nodes.
+#[derive(Clone)]
+pub(super) struct NodeDrag {
+    /// The node the user is dragging.
+    pub(super) leader: egui::Id,
+    /// The nodes moved by the drag that have not been placed yet.
+    pub(super) nodes: HashSet<egui::Id>,
+    /// The delta position of the nodes from their current position, in canvas units.
+    pub(super) delta: egui::Vec2,
+    /// The progress of the drag.
+    pub(super) phase: DragPhase,
+}
+
+/// The progress of a [`NodeDrag`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum DragPhase {
+    /// The user is dragging the nodes.
+    Dragging,
+    /// The user dropped the nodes during this frame.
+    ///
+    /// The nodes are placed when rendered.
+    Dropped,
 }
 
 impl<S> Default for EditorState<S> {
     fn default() -> Self {
         Self {
-            viewport_position: CanvasPos::ZERO,
-            grid: Grid { size: 10.0 },
-            dragged_node: None,
-            dragged_socket: None,
+            camera: ViewportState::default(),
+            grid: Grid::DEFAULT,
+            camera_animation: None,
+            content_rect: None,
+            minimap_world: None,
+            node_drag: None,
+            socket_drag: None,
+            connections: Vec::new(),
+            focused_node: None,
+            node_geometries: HashMap::new(),
+            selection: HashSet::new(),
+            marquee: None,
+            knife: None,
+            hovered_connection: None,
+            routes: RouteCache::default(),
         }
     }
 }
@@ -32,12 +121,78 @@ where
     Self: Clone + Send + Sync + 'static,
 {
     /// Loads the editor state.
-    pub(super) fn load(ctx: &egui::Context, id: egui::Id) -> Self {
-        ctx.data(|data| data.get_temp(id).unwrap_or_default())
+    ///
+    /// The state is moved out of the storage to avoid cloning it, and stored back when the
+    /// returned [`LoadedState`] is dropped.
+    pub(super) fn load(ctx: &egui::Context, id: egui::Id) -> LoadedState<S> {
+        let state = ctx.data_mut(|data| {
+            #[cfg_attr(not(feature = "serde"), allow(unused_mut))]
+            let mut state: Self = data.remove_temp(id).unwrap_or_default();
+
+            #[cfg(feature = "serde")]
+            if let Some(camera) = data.get_persisted(camera_id(id)) {
+                state.camera = camera;
+            }
+
+            state
+        });
+
+        LoadedState {
+            state,
+            ctx: ctx.clone(),
+            id,
+            store: Self::store,
+        }
     }
 
     /// Store the editor state.
     pub(super) fn store(self, ctx: &egui::Context, id: egui::Id) {
-        ctx.data_mut(|data| data.insert_temp(id, self));
+        ctx.data_mut(|data| {
+            #[cfg(feature = "serde")]
+            data.insert_persisted(camera_id(id), self.camera);
+
+            data.insert_temp(id, self);
+        });
+    }
+}
+
+/// The editor state loaded from the storage, stored back when dropped.
+///
+/// The state is kept even if `show_connections` is not called during a frame.
+pub(in crate::editor) struct LoadedState<S> {
+    /// The editor state.
+    state: EditorState<S>,
+    /// The context the state is stored in.
+    ctx: egui::Context,
+    /// The id of the editor.
+    id: egui::Id,
+    /// Stores the state, see [`EditorState::store`].
+    store: fn(EditorState<S>, &egui::Context, egui::Id),
+}
+
+impl<S> core::ops::Deref for LoadedState<S> {
+    type Target = EditorState<S>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.state
     }
 }
+
+impl<S> core::ops::DerefMut for LoadedState<S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.state
+    }
+}
+
+impl<S> Drop for LoadedState<S> {
+    fn drop(&mut self) {
+        let state = core::mem::take(&mut self.state);
+        (self.store)(state, &self.ctx, self.id);
+    }
+}
+
+/// The id used to persist the camera state of the editor.
+#[cfg(feature = "serde")]
+fn camera_id(id: egui::Id) -> egui::Id {
+    id.with("camera")
+}
@@ -12,8 +12,9 @@ mod socket;
 mod viewport;
 
 pub use editor::{
-    stages, ConnectionsUi, GraphEditor, GraphResponse, GraphUi, NodeResponse, NodeUi,
+    stages, Arrangement, ConnectionsUi, Flow, FlowPattern, GraphEditor, GraphResponse, GraphUi,
+    GridShape, GridStyle, KeyBindings, Minimap, NodeResponse, NodeUi, WaypointEdit,
 };
 pub use node::{Header, NodeLayout, TitleHeader};
 pub use socket::{ConnectionInProgress, NodeSide, RenderedSocket, Socket, SocketShape};
-pub use viewport::{Pos, Viewport};
+pub use viewport::{FreePos, GraphRect, NodePos, Pos, Snapping, Viewport, ViewportState};
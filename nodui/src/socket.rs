@@ -107,84 +107,196 @@ pub(crate) enum SocketInteraction<S> {
     Connect(S, S),
     /// The user is dragging a socket.
     InProgress(ConnectionInProgress<S>),
+    /// The user has picked up an existing connection from one of its sockets.
+    Detach(S, S),
+    /// The user has dropped a connection from a socket at a UI position where there is no socket.
+    Dropped(S, Pos2),
 }
 
 /// An in progress connection between two sockets.
 pub struct ConnectionInProgress<S> {
     /// The socket from which this connection has begin.
     pub source: RenderedSocket<S>,
-    /// The socket that is currently under the pointer, if any.
+    /// The socket the connection is attached to, if any.
+    ///
+    /// It is the socket under the pointer or the nearest one within the
+    /// [snap radius](crate::GraphEditor::connection_snap_radius).
     pub target: Option<RenderedSocket<S>>,
     /// The current position of the pointer.
     pub pointer_pos: Pos2,
 }
 
+impl<S> ConnectionInProgress<S> {
+    /// The UI position where the connection ends: the target socket if any, the pointer otherwise.
+    #[inline]
+    #[must_use]
+    pub fn end_pos(&self) -> Pos2 {
+        self.target
+            .as_ref()
+            .map_or(self.pointer_pos, RenderedSocket::pos)
+    }
+}
+
+/// Whether the user can connect a socket to another one.
+///
+/// See [`GraphEditor::can_connect`](crate::GraphEditor::can_connect).
+pub(crate) type CanConnect<'a, S> = dyn Fn(&S, &S) -> bool + 'a;
+
+/// A socket being dragged by the user.
+#[derive(Clone)]
+pub(crate) struct SocketDrag<S> {
+    /// The socket whose handle is grabbed by the pointer.
+    grabbed: S,
+    /// The socket from which the connection starts.
+    ///
+    /// It differs from `grabbed` when an existing connection has been picked up.
+    source: S,
+}
+
+impl<S> SocketDrag<S> {
+    /// The socket from which the connection starts.
+    pub(crate) fn source(&self) -> &S {
+        &self.source
+    }
+}
+
 /// Handle the socket responses.
 ///
 /// E.g. when the user drag-n-drop a socket to create a connection.
+///
+/// If `connections` is set, dragging an input socket (on the [left side](NodeSide::Left))
+/// picks up its connection.
+///
+/// The source socket and the sockets rejected by `can_connect` are never the target of the connection.
+/// If the pointer is not on a socket, the connection snaps to the nearest socket within `snap_radius`.
 pub(crate) fn handle_socket_responses<S>(
-    dragged_socket_id: &mut Option<S>,
+    socket_drag: &mut Option<SocketDrag<S>>,
     rendered_sockets: &[RenderedSocket<S>],
+    connections: Option<&[(S, S)]>,
+    can_connect: impl Fn(&S, &S) -> bool,
+    snap_radius: f32,
 ) -> SocketInteraction<S>
 where
     S: Clone + PartialEq,
 {
     let mut interaction = SocketInteraction::None;
 
-    if let Some(socket_id) = dragged_socket_id.as_ref() {
-        // There is a socket being dragged.
+    let find = |id: &S| rendered_sockets.iter().find(|s| &s.id == id);
 
-        let dragged_socket = rendered_sockets.iter().find(|s| &s.id == socket_id);
+    if let Some(drag) = socket_drag.as_ref() {
+        // There is a socket being dragged.
 
-        if let Some(socket) = dragged_socket {
+        if let (Some(grabbed), Some(source)) = (find(&drag.grabbed), find(&drag.source)) {
             // Check the response of the dragged socket.
 
-            if socket.response.drag_stopped() {
-                // The drag has stopped.
+            let pointer_pos = grabbed.response.interact_pointer_pos();
+
+            // The source is never its own target. When a connection has been picked up,
+            // the grabbed socket stays a valid target so the connection can be dropped back.
+            let target = find_target(rendered_sockets, pointer_pos, snap_radius, |s| {
+                s.id != drag.source && can_connect(&drag.source, &s.id)
+            });
 
-                let hovered = rendered_sockets.iter().find(|s| s.response.hovered());
+            if grabbed.response.drag_stopped() {
+                // The drag has stopped.
 
-                if let Some(hovered_socket) = hovered {
-                    // Another socket contains the pointer, the user want to connect the sockets.
+                if let Some(target) = target {
+                    // Another socket is under or near the pointer, the user want to connect the sockets.
 
                     interaction =
-                        SocketInteraction::Connect(socket_id.clone(), hovered_socket.id.clone());
-                } else {
+                        SocketInteraction::Connect(drag.source.clone(), target.id.clone());
+                } else if let Some(pointer_pos) = pointer_pos.filter(|_| {
+                    !rendered_sockets
+                        .iter()
+                        .any(|s| s.response.contains_pointer())
+                }) {
                     // The pointer is not on any socket.
-                    // Do nothing.
+
+                    interaction = SocketInteraction::Dropped(drag.source.clone(), pointer_pos);
                 }
 
                 // In all cases, reset the state.
-                *dragged_socket_id = None;
+                *socket_drag = None;
             } else {
                 // The dragging is still happening.
 
                 // Draw the on-going connection.
 
-                let hovered = rendered_sockets
-                    .iter()
-                    .find(|s| s.response.contains_pointer());
-
-                if let Some(pointer_pos) = socket.response.interact_pointer_pos() {
+                if let Some(pointer_pos) = pointer_pos {
                     interaction = SocketInteraction::InProgress(ConnectionInProgress {
-                        source: socket.clone(),
-                        target: hovered.cloned(),
+                        source: source.clone(),
+                        target: target.cloned(),
                         pointer_pos,
                     });
                 }
             }
         } else {
             // The currently dragged socket has been removed.
-            *dragged_socket_id = None;
+            *socket_drag = None;
         }
     } else if let Some(socket) = rendered_sockets.iter().find(|s| s.response.drag_started()) {
         // A socket is being dragged.
-        *dragged_socket_id = Some(socket.id.clone());
+
+        let connection = connections
+            .filter(|_| socket.side == NodeSide::Left)
+            .and_then(|connections| {
+                connections
+                    .iter()
+                    .find(|(a, b)| a == &socket.id || b == &socket.id)
+            });
+
+        let source = if let Some((a, b)) = connection {
+            // Pick up the connection and continue the drag from its other end.
+            interaction = SocketInteraction::Detach(a.clone(), b.clone());
+            if a == &socket.id {
+                b.clone()
+            } else {
+                a.clone()
+            }
+        } else {
+            socket.id.clone()
+        };
+
+        *socket_drag = Some(SocketDrag {
+            grabbed: socket.id.clone(),
+            source,
+        });
     }
 
     interaction
 }
 
+/// Finds the socket the dragged connection should be connected to.
+///
+/// If the pointer is over a socket, it is the target only if it is valid.
+/// Otherwise the target is the nearest valid socket within `snap_radius`.
+fn find_target<S>(
+    rendered_sockets: &[RenderedSocket<S>],
+    pointer_pos: Option<Pos2>,
+    snap_radius: f32,
+    is_valid: impl Fn(&RenderedSocket<S>) -> bool,
+) -> Option<&RenderedSocket<S>> {
+    let mut hovered = rendered_sockets
+        .iter()
+        .filter(|s| s.response.contains_pointer())
+        .peekable();
+
+    if hovered.peek().is_some() {
+        // Don't snap to a neighbor of a rejected socket.
+        return hovered.find(|s| is_valid(s));
+    }
+
+    let pointer_pos = pointer_pos?;
+
+    rendered_sockets
+        .iter()
+        .filter(|s| is_valid(s))
+        .map(|s| (s, s.pos().distance(pointer_pos)))
+        .filter(|(_, distance)| *distance <= snap_radius)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(s, _)| s)
+}
+
 /* -------------------------------------------------------------------------- */
 
 /// The shape of a socket's handle.
@@ -10,6 +10,8 @@ use egui::Vec2;
 pub struct Viewport {
     /// The offset of the viewport.
     pub(crate) position: Vec2,
+    /// The zoom factor of the viewport.
+    pub(crate) zoom: f32,
     /// The grid of the visual editor.
     pub(crate) grid: Grid,
 }
@@ -27,13 +29,30 @@ pub(crate) struct Grid {
 impl Viewport {
     /// Converts a UI position into a [`CanvasPos`].
     pub(crate) fn viewport_to_canvas(&self, pos: egui::Pos2) -> CanvasPos {
-        CanvasPos(pos - self.position)
+        CanvasPos(((pos - self.position).to_vec2() / self.zoom).to_pos2())
     }
 
     /// Converts a [`CanvasPos`] into a UI position.
     pub(crate) fn canvas_to_viewport(&self, pos: CanvasPos) -> egui::Pos2 {
         let CanvasPos(pos) = pos;
-        pos + self.position
+        (pos.to_vec2() * self.zoom).to_pos2() + self.position
+    }
+
+    /// The zoom factor of the viewport.
+    ///
+    /// A value greater than `1.0` means the graph appears bigger than its actual size.
+    #[must_use]
+    #[inline]
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Converts a UI rect into a [`CanvasRect`].
+    pub(crate) fn viewport_to_canvas_rect(&self, rect: egui::Rect) -> CanvasRect {
+        CanvasRect::from_two_pos(
+            self.viewport_to_canvas(rect.min),
+            self.viewport_to_canvas(rect.max),
+        )
     }
 
     #[must_use]
@@ -42,9 +61,34 @@ impl Viewport {
     pub fn viewport_to_graph(&self, pos: egui::Pos2) -> Pos {
         self.grid.canvas_to_graph(self.viewport_to_canvas(pos))
     }
+
+    #[must_use]
+    #[inline]
+    /// Converts a graph position into a UI position.
+    pub fn graph_to_viewport(&self, pos: Pos) -> egui::Pos2 {
+        self.canvas_to_viewport(self.grid.graph_to_canvas(pos))
+    }
+
+    #[must_use]
+    #[inline]
+    /// Converts a UI position into a free graph position.
+    pub fn viewport_to_free_pos(&self, pos: egui::Pos2) -> FreePos {
+        self.grid.canvas_to_free(self.viewport_to_canvas(pos))
+    }
+
+    #[must_use]
+    #[inline]
+    /// Converts a UI rect into the smallest graph rect that contains it.
+    pub fn viewport_to_graph_rect(&self, rect: egui::Rect) -> GraphRect {
+        self.grid
+            .canvas_rect_to_graph(self.viewport_to_canvas_rect(rect))
+    }
 }
 
 impl Grid {
+    /// The grid of the editor.
+    pub(crate) const DEFAULT: Grid = Grid { size: 10.0 };
+
     /// Converts a graph position into a canvas position.
     pub(crate) fn graph_to_canvas(&self, pos: Pos) -> CanvasPos {
         let Pos { x, y } = pos;
@@ -87,17 +131,127 @@ impl Grid {
         Pos { x, y }
     }
 
-    /// Converts a canvas position to a graph position by
-    /// rounding value to the nearest graph position.
-    pub(crate) fn canvas_to_graph_nearest(&self, pos: CanvasPos) -> Pos {
+    /// Converts a free graph position into a canvas position.
+    pub(crate) fn free_to_canvas(&self, pos: FreePos) -> CanvasPos {
+        let FreePos { x, y } = pos;
+        CanvasPos(egui::pos2(x * self.size, -y * self.size))
+    }
+
+    /// Converts a canvas position to a free graph position.
+    pub(crate) fn canvas_to_free(&self, pos: CanvasPos) -> FreePos {
         let (x, y) = self.canvas_to_graph_unrounded(pos);
+        FreePos { x, y }
+    }
 
-        #[allow(clippy::cast_possible_truncation)]
-        let x = x.round() as i32;
-        #[allow(clippy::cast_possible_truncation)]
-        let y = y.round() as i32;
+    /// Converts a graph rect into a canvas rect.
+    pub(crate) fn graph_rect_to_canvas(&self, rect: GraphRect) -> CanvasRect {
+        CanvasRect::from_two_pos(
+            self.graph_to_canvas(rect.min),
+            self.graph_to_canvas(rect.max),
+        )
+    }
 
-        Pos { x, y }
+    /// Converts a canvas rect into the smallest graph rect that contains it.
+    pub(crate) fn canvas_rect_to_graph(&self, rect: CanvasRect) -> GraphRect {
+        let CanvasRect(rect) = rect;
+
+        // `canvas_to_graph` rounds toward the top-left.
+        let top_left = self.canvas_to_graph(CanvasPos(rect.left_top()));
+        let bottom_right = {
+            let (x, y) = self.canvas_to_graph_unrounded(CanvasPos(rect.right_bottom()));
+
+            #[allow(clippy::cast_possible_truncation)]
+            // Round `x` toward +Inf (aka the right)
+            let x = x.ceil() as i32;
+
+            #[allow(clippy::cast_possible_truncation)]
+            // Round `y` toward -Inf (aka the bottom)
+            let y = y.floor() as i32;
+
+            Pos { x, y }
+        };
+
+        GraphRect::from_two_pos(top_left, bottom_right)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The position and zoom factor of the viewport of a [`GraphEditor`](crate::GraphEditor).
+///
+/// With the `serde` feature, the editor persists this state with [`egui::Memory`],
+/// so it is restored when the app restarts if egui persistence is enabled.
+/// It can also be saved and restored manually with [`GraphResponse::viewport_state`](crate::GraphResponse::viewport_state)
+/// and [`GraphEditor::viewport_state`](crate::GraphEditor::viewport_state).
+///
+/// Without the `serde` feature, save its [`position`](Self::position) and [`zoom`](Self::zoom)
+/// and restore it with [`ViewportState::new`].
+///
+/// # Example
+///
+/// ```
+/// # fn foo(response: &nodui::GraphResponse<u32>, editor: nodui::GraphEditor<nodui::stages::Settings<'_, u32>>) {
+/// let (position, zoom) = (response.viewport_state.position(), response.viewport_state.zoom());
+///
+/// // Later, e.g. when the app restarts.
+/// let editor = editor.viewport_state(nodui::ViewportState::new(position, zoom));
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ViewportState {
+    /// The position of the center of the viewport.
+    pub(crate) position: CanvasPos,
+    /// The zoom factor of the viewport.
+    pub(crate) zoom: f32,
+}
+
+impl Default for ViewportState {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            position: CanvasPos::ZERO,
+            zoom: 1.0,
+        }
+    }
+}
+
+impl ViewportState {
+    /// Creates a [`ViewportState`] centered on `position` with a `zoom` factor.
+    ///
+    /// The zoom factor is clamped by the editor, see [`GraphEditor::min_zoom`](crate::GraphEditor::min_zoom)
+    /// and [`GraphEditor::max_zoom`](crate::GraphEditor::max_zoom).
+    #[must_use]
+    #[inline]
+    pub fn new(position: impl NodePos, zoom: f32) -> Self {
+        Self {
+            position: Grid::DEFAULT.free_to_canvas(position.to_free_pos()),
+            zoom,
+        }
+    }
+
+    /// The position of the center of the viewport, in graph coordinates.
+    #[must_use]
+    #[inline]
+    pub fn position(&self) -> FreePos {
+        Grid::DEFAULT.canvas_to_free(self.position)
+    }
+
+    /// The zoom factor of the viewport.
+    #[must_use]
+    #[inline]
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Interpolates between `self` and `other`.
+    ///
+    /// The zoom factor is interpolated geometrically so zooming in and out feel the same.
+    pub(crate) fn lerp(self, other: ViewportState, t: f32) -> ViewportState {
+        let position = CanvasPos(self.position.0.lerp(other.position.0, t));
+        let zoom = self.zoom * (other.zoom / self.zoom).powf(t);
+
+        ViewportState { position, zoom }
     }
 }
 
@@ -105,7 +259,8 @@ impl Grid {
 
 /// An opaque intermediate value for conversion between graph coordinates
 /// and UI coordinates.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct CanvasPos(egui::Pos2);
 
 impl CanvasPos {
@@ -148,6 +303,32 @@ impl SubAssign<Vec2> for CanvasPos {
     }
 }
 
+/// A rectangle in the canvas coordinates system.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CanvasRect(egui::Rect);
+
+impl CanvasRect {
+    /// Creates the [`CanvasRect`] that has `a` and `b` as opposite corners.
+    pub(crate) fn from_two_pos(a: CanvasPos, b: CanvasPos) -> Self {
+        CanvasRect(egui::Rect::from_two_pos(a.0, b.0))
+    }
+
+    /// The center of the rect.
+    pub(crate) fn center(self) -> CanvasPos {
+        CanvasPos(self.0.center())
+    }
+
+    /// The size of the rect.
+    pub(crate) fn size(self) -> Vec2 {
+        self.0.size()
+    }
+
+    /// The smallest rect that contains both `self` and `other`.
+    pub(crate) fn union(self, other: CanvasRect) -> CanvasRect {
+        CanvasRect(self.0.union(other.0))
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 /// A position in the graph coordinates system.
@@ -170,3 +351,135 @@ impl Pos {
 }
 
 /* -------------------------------------------------------------------------- */
+
+/// A position in the graph coordinates system that is not bound to the grid.
+///
+/// Unlike [`Pos`], a node with a [`FreePos`] can be placed anywhere,
+/// see [`Snapping`] to control how it is snapped when moved by the user.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FreePos {
+    #[allow(missing_docs)]
+    pub x: f32,
+    #[allow(missing_docs)]
+    pub y: f32,
+}
+
+impl FreePos {
+    /// Creates a [`FreePos`].
+    #[inline]
+    #[must_use]
+    pub fn new(x: f32, y: f32) -> Self {
+        FreePos { x, y }
+    }
+}
+
+impl From<Pos> for FreePos {
+    #[inline]
+    fn from(pos: Pos) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        FreePos::new(pos.x as f32, pos.y as f32)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A type that can be used as the position of a node.
+///
+/// See [`GraphUi::node`](crate::GraphUi::node).
+pub trait NodePos: Copy {
+    /// Converts this position into a [`FreePos`].
+    fn to_free_pos(self) -> FreePos;
+
+    /// Creates a position from a [`FreePos`].
+    fn from_free_pos(pos: FreePos) -> Self;
+}
+
+impl NodePos for Pos {
+    #[inline]
+    fn to_free_pos(self) -> FreePos {
+        self.into()
+    }
+
+    /// Rounds the coordinates to the nearest grid position.
+    #[inline]
+    fn from_free_pos(pos: FreePos) -> Self {
+        #[allow(clippy::cast_possible_truncation)]
+        let x = pos.x.round() as i32;
+        #[allow(clippy::cast_possible_truncation)]
+        let y = pos.y.round() as i32;
+
+        Pos { x, y }
+    }
+}
+
+impl NodePos for FreePos {
+    #[inline]
+    fn to_free_pos(self) -> FreePos {
+        self
+    }
+
+    #[inline]
+    fn from_free_pos(pos: FreePos) -> Self {
+        pos
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// How the position of a node is snapped when the user moves it.
+///
+/// Note that a node with a [`Pos`] is always snapped to the grid.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Snapping {
+    /// The position is not snapped.
+    None,
+    /// The position is snapped to the grid.
+    #[default]
+    Grid,
+    /// The position is snapped to a multiple of the step, in graph units.
+    Step(f32),
+}
+
+impl Snapping {
+    /// Snaps a position.
+    pub(crate) fn snap(self, pos: FreePos) -> FreePos {
+        let step = match self {
+            Snapping::Grid => 1.0,
+            Snapping::Step(step) if step > 0.0 => step,
+            Snapping::None | Snapping::Step(_) => return pos,
+        };
+
+        FreePos {
+            x: (pos.x / step).round() * step,
+            y: (pos.y / step).round() * step,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A rectangle in the graph coordinates system.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GraphRect {
+    /// The bottom-left corner of the rect.
+    pub min: Pos,
+    /// The top-right corner of the rect.
+    pub max: Pos,
+}
+
+impl GraphRect {
+    /// Creates the [`GraphRect`] that has `a` and `b` as opposite corners.
+    #[inline]
+    #[must_use]
+    pub fn from_two_pos(a: Pos, b: Pos) -> Self {
+        GraphRect {
+            min: Pos::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Pos::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */